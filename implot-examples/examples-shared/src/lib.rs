@@ -3,7 +3,7 @@ pub mod heatmaps;
 pub mod line_plots;
 pub mod scatter_plots;
 pub mod stairs_plots;
-mod stem_plots;
+pub mod stem_plots;
 pub mod text_plots;
 
 use imgui::{Condition, Ui, Window};
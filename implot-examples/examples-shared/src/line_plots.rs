@@ -4,11 +4,10 @@
 use imgui::{CollapsingHeader, Condition, Ui};
 use implot::{
     get_plot_limits, get_plot_mouse_position, get_plot_query, is_legend_entry_hovered,
-    is_plot_hovered, is_plot_queried, pixels_to_plot_vec2, plot_to_pixels_vec2, push_style_color,
-    push_style_var_f32, push_style_var_i32, set_colormap_from_preset, set_colormap_from_vec,
-    set_plot_y_axis, AxisFlags, Colormap, ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4,
-    Marker, Plot, PlotColorElement, PlotFlags, PlotLine, PlotLocation, PlotOrientation, PlotUi,
-    StyleVar, YAxisChoice,
+    is_plot_hovered, is_plot_queried, pixels_to_plot_vec2, plot_to_pixels_vec2,
+    set_colormap_from_preset, set_colormap_from_vec, set_plot_y_axis, AxisFlags, Colormap,
+    ImPlotLimits, ImPlotPoint, ImPlotRange, ImVec2, ImVec4, LegendFlags, Marker, Plot,
+    PlotColorElement, PlotFlags, PlotLine, PlotLocation, PlotUi, StyleVar, YAxisChoice,
 };
 
 use std::{cell::RefCell, rc::Rc};
@@ -148,7 +147,10 @@ impl LinePlotDemoState {
             .with_plot_flags(&plot_flags)
             .with_x_axis_flags(&x_axis_flags)
             .with_y_axis_flags(YAxisChoice::First, &y_axis_flags)
-            .with_legend_location(&PlotLocation::West, &PlotOrientation::Horizontal, true)
+            .with_legend(
+                PlotLocation::West,
+                LegendFlags::HORIZONTAL | LegendFlags::OUTSIDE,
+            )
             .build(plot_ui, || {
                 PlotLine::new("A line 2").plot(&[2.4, 2.9], &[1.1, 1.9]);
             });
@@ -179,8 +181,9 @@ impl LinePlotDemoState {
             .with_plot_flags(&(PlotFlags::NONE | PlotFlags::QUERY))
             .build(plot_ui, || {
                 if is_plot_hovered() {
-                    hover_pos_plot = Some(get_plot_mouse_position(None));
-                    hover_pos_pixels = Some(plot_to_pixels_vec2(&(hover_pos_plot.unwrap()), None));
+                    hover_pos_plot = Some(get_plot_mouse_position(None, None));
+                    hover_pos_pixels =
+                        Some(plot_to_pixels_vec2(&(hover_pos_plot.unwrap()), None, None));
                 }
 
                 // Getting the plot position from pixels also works when the plot is not hovered,
@@ -191,6 +194,7 @@ impl LinePlotDemoState {
                         y: ui.io().mouse_pos[1],
                     },
                     None,
+                    None,
                 ));
 
                 // Plot a line so we have a legend entry
@@ -202,7 +206,7 @@ impl LinePlotDemoState {
                 if is_plot_queried() {
                     query_limits = Some(get_plot_query(None));
                 }
-                plot_limits = Some(get_plot_limits(None));
+                plot_limits = Some(get_plot_limits(None, None));
             });
 
         // Print some previously-exfiltrated info. This is because calling
@@ -246,9 +250,10 @@ impl LinePlotDemoState {
 
         // The style stack works the same as for other imgui things - we can push
         // things to have them apply, then pop again to undo the change. In implot-rs,
-        // pushing returns a value on which we have to call .pop() later. Pushing
-        // variables can be done outside of plot calls as well.
-        let style = push_style_color(&PlotColorElement::PlotBg, 1.0, 1.0, 1.0, 0.2);
+        // pushing returns a token that pops the change when it is dropped, or when
+        // .pop() is called on it. Pushing variables can be done outside of plot calls
+        // as well.
+        let style = plot_ui.push_style_color(&PlotColorElement::PlotBg, [1.0, 1.0, 1.0, 0.2]);
         Plot::new("Style demo plot")
             .size([content_width, 300.0])
             .x_limits(ImPlotRange { Min: 0.0, Max: 6.0 }, Condition::Always)
@@ -263,18 +268,19 @@ impl LinePlotDemoState {
             .with_plot_flags(&(PlotFlags::NONE))
             .with_y_axis_flags(YAxisChoice::First, &(AxisFlags::NONE))
             .build(plot_ui, || {
-                // Markers can be selected as shown here. The markers are internally represented
-                // as an u32, hence this calling style.
-                let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
+                // Markers can be selected as shown here. Each style variable carries the value
+                // it is pushed with.
+                let markerchoice = plot_ui.push_style_var(StyleVar::Marker(Marker::Cross));
                 PlotLine::new("Left eye").plot(&[2.0, 2.0], &[2.0, 1.0]);
                 // Calling pop() on the return value of the push above will undo the marker choice.
                 markerchoice.pop();
 
                 // Line weights can be set the same way, along with some other things - see
-                // the docs of StyleVar for more info.
-                let lineweight = push_style_var_f32(&StyleVar::LineWeight, 5.0);
-                PlotLine::new("Right eye").plot(&[4.0, 4.0], &[2.0, 1.0]);
-                lineweight.pop();
+                // the docs of StyleVar for more info. The closure-based helper pops the
+                // change again once the closure returns.
+                plot_ui.with_style_var(StyleVar::LineWeight(5.0), || {
+                    PlotLine::new("Right eye").plot(&[4.0, 4.0], &[2.0, 1.0]);
+                });
 
                 let x_values = vec![1.0, 2.0, 4.0, 5.0];
                 let y_values = vec![1.0, 0.0, 0.0, 1.0];
@@ -2,7 +2,7 @@
 //! features of the libray, see the line_plots example.
 
 use imgui::{CollapsingHeader, Ui};
-use implot::{push_style_var_f32, push_style_var_i32, Marker, Plot, PlotScatter, PlotUi, StyleVar};
+use implot::{Marker, Plot, PlotScatter, PlotUi, StyleVar};
 
 pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
     ui.text("This header just draws a scatter plot with as little code as possible.");
@@ -30,19 +30,18 @@ pub fn show_custom_markers_plot(ui: &Ui, plot_ui: &PlotUi) {
             // Change to cross marker for one scatter plot call
             let x_positions = vec![0.1, 0.2, 0.1, 0.5, 0.9];
             let y_positions = vec![0.1, 0.1, 0.3, 0.3, 0.9];
-            let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
+            let markerchoice = plot_ui.push_style_var(StyleVar::Marker(Marker::Cross));
             PlotScatter::new("legend label 1").plot(&x_positions, &y_positions);
             markerchoice.pop();
 
             // One can combine things like marker size and markor choice
             let x_positions = vec![0.4, 0.1];
             let y_positions = vec![0.5, 0.3];
-            let marker_choice = push_style_var_i32(&StyleVar::Marker, Marker::Diamond as i32);
-            let marker_size = push_style_var_f32(&StyleVar::MarkerSize, 12.0);
+            let marker_choice = plot_ui.push_style_var(StyleVar::Marker(Marker::Diamond));
+            let marker_size = plot_ui.push_style_var(StyleVar::MarkerSize(12.0));
             PlotScatter::new("legend label 2").plot(&x_positions, &y_positions);
 
-            // TODO(4bb4) check if these have to be in reverse push order. Does not
-            // seem to be the case.
+            // These have to be popped in reverse push order, which is checked in debug builds
             marker_size.pop();
             marker_choice.pop();
         });
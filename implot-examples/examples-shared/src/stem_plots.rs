@@ -21,8 +21,23 @@ pub fn show_basic_plot(ui: &Ui, plot_ui: &PlotUi) {
         });
 }
 
+pub fn show_horizontal_plot(ui: &Ui, plot_ui: &PlotUi) {
+    ui.text("This header shows horizontal stems, with implicit positions and integer values.");
+    let content_width = ui.window_content_region_width();
+    Plot::new("Horizontal stem plot")
+        .size([content_width, 300.0])
+        .build(plot_ui, || {
+            let values: [i32; 6] = [3, -1, 4, 1, -5, 9];
+            PlotStems::new("legend label")
+                .with_horizontal_stems()
+                .with_positions(0.5, 1.0)
+                .plot_values(&values);
+        });
+}
+
 pub fn show_demo_headers(ui: &Ui, plot_ui: &PlotUi) {
     if CollapsingHeader::new("Stem plots").build(ui) {
         show_basic_plot(ui, plot_ui);
+        show_horizontal_plot(ui, plot_ui);
     }
 }
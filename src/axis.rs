@@ -0,0 +1,505 @@
+//! # Axis module
+//!
+//! This module defines the `AxisConfig` struct, which holds everything that can be set up for
+//! one of the axes of a plot, along with the flags and scale choices that go with it. Configs
+//! are attached to plots with [`Plot::with_axis`](struct.Plot.html#method.with_axis).
+use crate::Axis;
+use bitflags::bitflags;
+use imgui::Condition;
+use implot_sys as sys;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::{cell::RefCell, rc::Rc};
+use sys::ImPlotRange;
+
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotAxisFlags"))]
+bitflags! {
+    /// Axis flags. Documentation copied from implot.h for convenience. ImPlot itself also
+    /// has `Lock`, which combines `LOCK_MIN` and `LOCK_MAX`, and `NoDecorations`, which combines
+    /// `NO_LABEL`, `NO_GRID_LINES`, `NO_TICK_MARKS` and `NO_TICK_LABELS`.
+    #[repr(transparent)]
+    pub struct AxisFlags: u32 {
+        /// "Default" according to original docs
+        const NONE = sys::ImPlotAxisFlags__ImPlotAxisFlags_None;
+        /// The axis label will not be displayed (axis labels also hidden if no label is set)
+        const NO_LABEL = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoLabel;
+        /// Grid lines will not be displayed
+        const NO_GRID_LINES = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoGridLines;
+        /// Tick marks will not be displayed
+        const NO_TICK_MARKS = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoTickMarks;
+        /// Text labels will not be displayed
+        const NO_TICK_LABELS = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoTickLabels;
+        /// Axis will not be initially fit to data extents on the first rendered frame
+        const NO_INITIAL_FIT = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoInitialFit;
+        /// The user will not be able to open context menus with right-click
+        const NO_MENUS = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoMenus;
+        /// Axis ticks and labels will be rendered on conventionally opposite side (i.e, right
+        /// or top)
+        const OPPOSITE = sys::ImPlotAxisFlags__ImPlotAxisFlags_Opposite;
+        /// Grid lines will be displayed in the foreground (i.e. on top of data) instead of the
+        /// background
+        const FOREGROUND = sys::ImPlotAxisFlags__ImPlotAxisFlags_Foreground;
+        /// A logartithmic (base 10) axis scale will be used (mutually exclusive with AxisFlags::TIME)
+        const LOG_SCALE = sys::ImPlotAxisFlags__ImPlotAxisFlags_LogScale;
+        /// Axis will display date/time formatted labels (mutually exclusive with AxisFlags::LOG_SCALE)
+        const TIME = sys::ImPlotAxisFlags__ImPlotAxisFlags_Time;
+        /// The axis will be inverted
+        const INVERT = sys::ImPlotAxisFlags__ImPlotAxisFlags_Invert;
+        /// Axis will be auto-fitting to data extents
+        const AUTO_FIT = sys::ImPlotAxisFlags__ImPlotAxisFlags_AutoFit;
+        /// Axis will only fit points if the point is in the visible range of the orthogonal axis
+        const RANGE_FIT = sys::ImPlotAxisFlags__ImPlotAxisFlags_RangeFit;
+        /// The axis minimum value will be locked when panning/zooming
+        const LOCK_MIN = sys::ImPlotAxisFlags__ImPlotAxisFlags_LockMin;
+        /// The axis maximum value will be locked when panning/zooming
+        const LOCK_MAX = sys::ImPlotAxisFlags__ImPlotAxisFlags_LockMax;
+        /// Default flags of the secondary axes (X2, X3, Y2 and Y3): no grid lines, and ticks
+        /// and labels on the opposite side
+        const AUX_DEFAULT = sys::ImPlotAxisFlags__ImPlotAxisFlags_AuxDefault;
+    }
+}
+
+/// Scale of an axis. The version of ImPlot we link to expresses these through the `LOG_SCALE`
+/// and `TIME` axis flags, this enum is a way to pick one of them without having to worry about
+/// them being mutually exclusive.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AxisScale {
+    /// Default linear scale
+    Linear,
+    /// Logarithmic (base 10) scale
+    Log10,
+    /// Linear scale with values interpreted as UNIX timestamps, with date/time formatted labels
+    Time,
+}
+
+impl AxisScale {
+    /// The axis flags that correspond to this scale
+    fn flags(self) -> AxisFlags {
+        match self {
+            AxisScale::Linear => AxisFlags::NONE,
+            AxisScale::Log10 => AxisFlags::LOG_SCALE,
+            AxisScale::Time => AxisFlags::TIME,
+        }
+    }
+}
+
+/// Internally-used struct for storing axis limits
+#[derive(Clone)]
+enum AxisLimitSpecification {
+    /// Direct limits, specified as values
+    Single(ImPlotRange, Condition),
+    /// Limits that are linked to limits of other plots (via clones of the same Rc)
+    Linked(Rc<RefCell<ImPlotRange>>),
+}
+
+/// Internally-used struct for storing where custom axis ticks go
+#[derive(Clone)]
+enum TickPositions {
+    /// Ticks at explicitly given positions
+    Explicit(Vec<f64>),
+    /// A given number of ticks, evenly spaced between (and including) a minimum and maximum
+    EvenlySpaced { min: f64, max: f64, count: u32 },
+}
+
+/// Internally-used struct for storing custom axis ticks
+#[derive(Clone)]
+struct AxisTicksSpecification {
+    /// Where the ticks are placed
+    positions: TickPositions,
+    /// Labels for the ticks, if any. I'd prefer to store these together with the positions in
+    /// one vector of an algebraic data type, but this would mean extra copies when it comes time
+    /// to draw the plot because the C++ library expects separate lists. The data is stored as
+    /// CStrings because those are null-terminated, and since we have to convert to
+    /// null-terminated data anyway, we may as well do that directly instead of cloning Strings
+    /// and converting them afterwards.
+    labels: Option<Vec<CString>>,
+    /// Whether to also show the default ticks when showing custom ticks or not
+    keep_default: bool,
+}
+
+/// Internal helper function to convert tick labels to CStrings
+///
+/// # Panics
+/// Will panic if any of the tick label strings contain internal null bytes.
+fn tick_labels_to_cstrings<'a, I: Iterator<Item = &'a str>>(labels: I) -> Vec<CString> {
+    labels
+        .map(|label| {
+            CString::new(label)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label))
+        })
+        .collect()
+}
+
+/// Configuration of a single plot axis. This collects everything ImPlot's `SetupAxis*` family
+/// of functions can set for an axis, and is attached to a plot for one of the six axes:
+/// ```no_run
+/// # use implot::{Axis, AxisConfig, AxisFlags, Condition, Plot};
+/// let plotting_context = implot::Context::create();
+/// let plot_ui = plotting_context.get_plot_ui();
+/// Plot::new("my title")
+///     .with_axis(
+///         Axis::Y2,
+///         AxisConfig::new()
+///             .label("Temperature")
+///             .with_flags(&(AxisFlags::AUX_DEFAULT | AxisFlags::LOCK_MIN))
+///             .limits([-10.0, 40.0], Condition::Once),
+///     )
+///     .build(&plot_ui, || {
+///         // Plot things, using implot::set_axis(Axis::Y2) to put them on the second Y axis
+///     });
+/// ```
+/// X1 and Y1 are always enabled, the other axes are enabled by attaching a config to them.
+/// Unless flags are set, X1 and Y1 use `AxisFlags::NONE` and the other axes use
+/// `AxisFlags::AUX_DEFAULT`, which puts them on the opposite side without grid lines.
+#[rustversion::attr(since(1.48), doc(alias = "SetupAxis"))]
+#[derive(Clone)]
+pub struct AxisConfig {
+    /// Label of the axis, if any. Stored as CString because that's what we'll use afterwards,
+    /// and this ensures the CString itself will stay alive long enough for the plot.
+    label: Option<CString>,
+    /// Flags relating to the axis, if set. Otherwise, the default flags of the axis are used.
+    flags: Option<AxisFlags>,
+    /// Scale of the axis, if set. Overrides the scale-related bits in `flags`.
+    scale: Option<AxisScale>,
+    /// Axis limits, if present
+    limits: Option<AxisLimitSpecification>,
+    /// Custom ticks, if present
+    ticks: Option<AxisTicksSpecification>,
+    /// C-style format string for the tick labels, if set
+    format: Option<CString>,
+}
+
+impl AxisConfig {
+    /// Create a new axis configuration that leaves everything at ImPlot's defaults.
+    pub fn new() -> Self {
+        Self {
+            label: None,
+            flags: None,
+            scale: None,
+            limits: None,
+            ticks: None,
+            format: None,
+        }
+    }
+
+    /// Set the label of the axis
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    #[inline]
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(
+            CString::new(label)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label)),
+        );
+        self
+    }
+
+    /// Set the axis flags, see the help for `AxisFlags` for what the available flags are. These
+    /// replace the default flags of the axis, so combine them with `AxisFlags::AUX_DEFAULT` to
+    /// keep a secondary axis on the opposite side.
+    #[inline]
+    pub fn with_flags(mut self, flags: &AxisFlags) -> Self {
+        self.flags = Some(*flags);
+        self
+    }
+
+    /// Set the scale of the axis. This takes precedence over the `LOG_SCALE` and `TIME` bits
+    /// of the flags set with `with_flags`.
+    #[inline]
+    pub fn with_scale(mut self, scale: AxisScale) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Set the format of the tick labels as a C-style format string, which receives the tick
+    /// value as a double. The default is `"%g"`.
+    ///
+    /// # Panics
+    /// Will panic if the format string contains internal null bytes.
+    ///
+    /// # Safety
+    /// This function directly sets the format string of a C formatting function (`snprintf`).
+    /// The format string has to consume exactly one `double` argument (for example `"%.2f s"`),
+    /// anything else is undefined behavior once the plot is drawn. While the string is not used
+    /// until later and hence the function here is strictly speaking safe, the effect of this
+    /// function can lead to unsoundness later, hence it is marked as unsafe.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisFormat"))]
+    #[inline]
+    pub unsafe fn with_format(mut self, format: &str) -> Self {
+        self.format = Some(
+            CString::new(format)
+                .unwrap_or_else(|_| panic!("Format string has internal null bytes: {}", format)),
+        );
+        self
+    }
+
+    /// Set the limits of the axis. The `condition` determines when the limits are applied,
+    /// `Condition::Always` forces them on every frame while `Condition::Once` only sets them
+    /// when the plot is first shown.
+    ///
+    /// Note: This conflicts with `linked_limits`, whichever is called last takes effect.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisLimits"))]
+    #[inline]
+    pub fn limits<L: Into<ImPlotRange>>(mut self, limits: L, condition: Condition) -> Self {
+        self.limits = Some(AxisLimitSpecification::Single(limits.into(), condition));
+        self
+    }
+
+    /// Set linked limits for the axis. Pass clones of the same `Rc` into other plots (or other
+    /// axes of other plots) to link their limits with the same values - zooming or panning one
+    /// of them then moves all of them.
+    ///
+    /// The range is read when the plot is begun and written back when it is ended, so it must
+    /// not be borrowed at either of these points, which panics. The same range can be linked to
+    /// several axes of one plot, e.g. to zoom the X and Y axes together.
+    ///
+    /// Note: This conflicts with `limits`, whichever is called last takes effect.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisLinks"))]
+    #[inline]
+    pub fn linked_limits(mut self, limits: Rc<RefCell<ImPlotRange>>) -> Self {
+        self.limits = Some(AxisLimitSpecification::Linked(limits));
+        self
+    }
+
+    /// Set ticks without labels, at the given positions. The `show_default` setting determines
+    /// whether the default ticks are also shown.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn ticks(mut self, ticks: &[f64], show_default: bool) -> Self {
+        self.ticks = Some(AxisTicksSpecification {
+            positions: TickPositions::Explicit(ticks.into()),
+            labels: None,
+            keep_default: show_default,
+        });
+        self
+    }
+
+    /// Set ticks with labels. The slice contains one position and label each in the form of a
+    /// tuple `(label_position, label_string)`. The `show_default` setting determines whether the
+    /// default ticks are also shown.
+    ///
+    /// # Panics
+    /// Will panic if any of the tick label strings contain internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn ticks_with_labels(mut self, tick_labels: &[(f64, String)], show_default: bool) -> Self {
+        self.ticks = Some(AxisTicksSpecification {
+            positions: TickPositions::Explicit(tick_labels.iter().map(|x| x.0).collect()),
+            labels: Some(tick_labels_to_cstrings(
+                tick_labels.iter().map(|x| x.1.as_str()),
+            )),
+            keep_default: show_default,
+        });
+        self
+    }
+
+    /// Set `count` ticks without labels, evenly spaced between `min` and `max` (both inclusive).
+    /// The `show_default` setting determines whether the default ticks are also shown.
+    ///
+    /// # Panics
+    /// Will panic if `count` is smaller than two, since that can't span a range.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn ticks_range(mut self, min: f64, max: f64, count: u32, show_default: bool) -> Self {
+        assert!(
+            count >= 2,
+            "Evenly spaced ticks need a count of at least two"
+        );
+        self.ticks = Some(AxisTicksSpecification {
+            positions: TickPositions::EvenlySpaced { min, max, count },
+            labels: None,
+            keep_default: show_default,
+        });
+        self
+    }
+
+    /// Set ticks with labels, evenly spaced between `min` and `max` (both inclusive). One tick
+    /// is created per label. The `show_default` setting determines whether the default ticks are
+    /// also shown.
+    ///
+    /// # Panics
+    /// Will panic if there are fewer than two labels, or if any of the tick label strings contain
+    /// internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn ticks_range_with_labels(
+        mut self,
+        min: f64,
+        max: f64,
+        labels: &[&str],
+        show_default: bool,
+    ) -> Self {
+        assert!(
+            labels.len() >= 2,
+            "Evenly spaced ticks need at least two labels"
+        );
+        self.ticks = Some(AxisTicksSpecification {
+            positions: TickPositions::EvenlySpaced {
+                min,
+                max,
+                count: labels.len() as u32,
+            },
+            labels: Some(tick_labels_to_cstrings(labels.iter().copied())),
+            keep_default: show_default,
+        });
+        self
+    }
+
+    /// Internal helper function to apply this configuration to the given axis of the current
+    /// plot. This has to be called between `BeginPlot` and `SetupFinish`, since that is where
+    /// ImPlot accepts setup calls.
+    ///
+    /// For linked limits, ImPlot holds on to pointers to the linked values until `EndPlot`, where
+    /// it writes the (possibly user-modified) limits back. These pointers point to the copies in
+    /// `linked_limits`, which the caller has to keep alive until the plot is ended.
+    pub(crate) fn setup(&self, axis: Axis, linked_limits: &mut LinkedLimits) {
+        let flags = self.flags.unwrap_or(match axis {
+            Axis::X1 | Axis::Y1 => AxisFlags::NONE,
+            _ => AxisFlags::AUX_DEFAULT,
+        });
+        let flags = match self.scale {
+            Some(scale) => (flags - AxisFlags::LOG_SCALE - AxisFlags::TIME) | scale.flags(),
+            None => flags,
+        };
+        let axis = axis as sys::ImAxis;
+
+        // The axis itself has to be set up first, the other setup calls expect it to be enabled
+        unsafe {
+            sys::ImPlot_SetupAxis(
+                axis,
+                self.label
+                    .as_ref()
+                    .map_or(std::ptr::null(), |label| label.as_ptr()),
+                flags.bits() as sys::ImPlotAxisFlags,
+            );
+        }
+
+        // The format has to come before the ticks, since ImPlot uses it to create tick labels
+        // when custom ticks are set up without labels.
+        if let Some(format) = &self.format {
+            unsafe {
+                sys::ImPlot_SetupAxisFormat_Str(axis, format.as_ptr());
+            }
+        }
+
+        match &self.limits {
+            Some(AxisLimitSpecification::Single(limits, condition)) => unsafe {
+                sys::ImPlot_SetupAxisLimits(
+                    axis,
+                    limits.Min,
+                    limits.Max,
+                    *condition as sys::ImPlotCond,
+                );
+            },
+            Some(AxisLimitSpecification::Linked(value)) => {
+                let copy = linked_limits.copy_of(value);
+                unsafe {
+                    sys::ImPlot_SetupAxisLinks(
+                        axis,
+                        std::ptr::addr_of_mut!((*copy).Min),
+                        std::ptr::addr_of_mut!((*copy).Max),
+                    );
+                }
+            }
+            None => {}
+        }
+
+        if let Some(ticks) = &self.ticks {
+            // ImPlot copies the labels while setting up the ticks, so the pointer vector
+            // only has to live until the end of this block.
+            let label_pointers = ticks.labels.as_ref().map(|labels| {
+                labels
+                    .iter()
+                    .map(|label| label.as_ptr())
+                    .collect::<Vec<*const c_char>>()
+            });
+            let labels_pointer = label_pointers
+                .as_ref()
+                .map_or(std::ptr::null(), |pointers| pointers.as_ptr());
+
+            match &ticks.positions {
+                // No ticks means nothing to do, ImPlot doesn't like empty tick lists
+                TickPositions::Explicit(positions) if positions.is_empty() => {}
+                TickPositions::Explicit(positions) => unsafe {
+                    sys::ImPlot_SetupAxisTicks_doublePtr(
+                        axis,
+                        positions.as_ptr(),
+                        positions.len() as i32, // "as" casts saturate as of Rust 1.45
+                        labels_pointer,
+                        ticks.keep_default,
+                    );
+                },
+                TickPositions::EvenlySpaced { min, max, count } => unsafe {
+                    sys::ImPlot_SetupAxisTicks_double(
+                        axis,
+                        *min,
+                        *max,
+                        *count as i32, // "as" casts saturate as of Rust 1.45
+                        labels_pointer,
+                        ticks.keep_default,
+                    );
+                },
+            }
+        }
+    }
+
+    /// Internal helper function to get the linked range of the axis, if it has one
+    pub(crate) fn linked_range(&self) -> Option<&Rc<RefCell<ImPlotRange>>> {
+        match &self.limits {
+            Some(AxisLimitSpecification::Linked(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// The linked limits of a plot while it is drawn. ImPlot reads from and writes to linked limits
+/// from `BeginPlot` until `EndPlot`, so it gets pointers to copies of the linked ranges instead of
+/// the ranges themselves. This way, no borrow has to be held while the plot is drawn, and the
+/// ranges can be used freely within the plot closure. Axes linked to the same range share a copy.
+#[derive(Default)]
+pub(crate) struct LinkedLimits {
+    /// The linked ranges and their copies. The copies are boxed so the pointers given to ImPlot
+    /// stay valid when this struct is moved.
+    links: Vec<(Rc<RefCell<ImPlotRange>>, Box<ImPlotRange>)>,
+}
+
+impl LinkedLimits {
+    /// Internal helper function to get a pointer to the copy of the given range, copying it
+    /// first if it isn't there yet.
+    ///
+    /// # Panics
+    /// Will panic if the range is currently mutably borrowed.
+    pub(crate) fn copy_of(&mut self, limits: &Rc<RefCell<ImPlotRange>>) -> *mut ImPlotRange {
+        let index = match self.links.iter().position(|(l, _)| Rc::ptr_eq(l, limits)) {
+            Some(index) => index,
+            None => {
+                let value = *limits
+                    .try_borrow()
+                    .expect("Linked axis limits are mutably borrowed while the plot is begun");
+                self.links.push((limits.clone(), Box::new(value)));
+                self.links.len() - 1
+            }
+        };
+        &mut *self.links[index].1 as *mut ImPlotRange
+    }
+
+    /// Internal helper function to write the copies back to the linked ranges, to be called
+    /// once ImPlot is done with them after `EndPlot`.
+    ///
+    /// # Panics
+    /// Will panic if one of the ranges is currently borrowed.
+    pub(crate) fn write_back(&self) {
+        for (limits, copy) in &self.links {
+            *limits
+                .try_borrow_mut()
+                .expect("Linked axis limits are borrowed while the plot is ended") = **copy;
+        }
+    }
+}
+
+impl Default for AxisConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -5,7 +5,9 @@
 use parking_lot::ReentrantMutex;
 
 use crate::sys;
-use crate::PlotUi;
+#[cfg(feature = "serde")]
+use crate::Theme;
+use crate::{PlotUi, Style};
 /// An implot context.
 ///
 /// A context is required to do most of the things this library provides. While this was created
@@ -46,42 +48,77 @@ impl Context {
     /// Get a "plot ui" struct, this will be used to build actual plots and is quite
     /// analogous to imgui-rs' "Ui" struct.
     pub fn get_plot_ui(&self) -> PlotUi {
-        PlotUi { context: self }
+        PlotUi::new(self)
     }
 
-    /// Use light colors for the implot style.
-    ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
+    /// Internal helper function to get the raw style of this context. This goes through
+    /// `addr_of_mut!`, since a `&mut` to the style would alias references handed out by `style()`.
+    fn raw_style(&self) -> *mut sys::ImPlotStyle {
+        unsafe { std::ptr::addr_of_mut!((*self.raw).Style) }
+    }
+
+    /// Get the style of this context, with the sizes, colors and other settings used for
+    /// plotting. See [`Style`] for what it contains.
+    #[rustversion::attr(since(1.48), doc(alias = "GetStyle"))]
+    pub fn style(&self) -> &Style {
+        // This is fine because Style is a transparent wrapper around ImPlotStyle, and the
+        // style lives as long as the context.
+        unsafe { &*(self.raw_style() as *const Style) }
+    }
+
+    /// Get the style of this context for changing it. Changes apply to everything plotted
+    /// afterwards. See [`Style`] for what it contains.
+    pub fn style_mut(&mut self) -> &mut Style {
+        unsafe { &mut *(self.raw_style() as *mut Style) }
+    }
+
+    /// Capture the current style of this context as a theme, which can be saved and applied
+    /// again later. See [`Theme`] for details.
+    #[cfg(feature = "serde")]
+    pub fn capture_theme(&self) -> Theme {
+        Theme::capture(self.style())
+    }
+
+    /// Apply a theme to the style of this context. Applying a theme captured with
+    /// [`Context::capture_theme`] restores exactly the style it was captured from.
+    #[cfg(feature = "serde")]
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        theme.apply(self.style_mut());
+    }
+
+    /// Use automatically picked colors for the implot style, which are based on the imgui
+    /// style. This is what a new context starts out with.
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsAuto"))]
+    pub fn use_auto_colors(&self) {
+        unsafe {
+            sys::ImPlot_StyleColorsAuto(self.raw_style());
+        }
+    }
+
+    /// Use light colors for the implot style. For everything else in the style, see
+    /// [`Context::style_mut`].
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsLight"))]
     pub fn use_light_colors(&self) {
         unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsLight(style);
+            sys::ImPlot_StyleColorsLight(self.raw_style());
         }
     }
 
-    /// Use dark colors for the implot style.
-    ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
+    /// Use dark colors for the implot style. For everything else in the style, see
+    /// [`Context::style_mut`].
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsDark"))]
     pub fn use_dark_colors(&self) {
         unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsDark(style);
+            sys::ImPlot_StyleColorsDark(self.raw_style());
         }
     }
 
-    /// Use classic colors for the implot style.
-    ///
-    /// This will eventually be exposed more thoroughly in the form of ImPlotStyle,
-    /// but for now this allows one to at least easily set the color preset.
+    /// Use classic colors for the implot style. For everything else in the style, see
+    /// [`Context::style_mut`].
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsClassic"))]
     pub fn use_classic_colors(&self) {
         unsafe {
-            let style = sys::ImPlot_GetStyle();
-            assert_ne!(style, std::ptr::null_mut());
-            sys::ImPlot_StyleColorsClassic(style);
+            sys::ImPlot_StyleColorsClassic(self.raw_style());
         }
     }
 }
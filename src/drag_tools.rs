@@ -0,0 +1,315 @@
+//! # Drag tools module
+//!
+//! This module defines the interactive tools that can be placed in a plot and dragged around
+//! by the user: points, vertical and horizontal lines and rectangles. They all work directly on
+//! values owned by the caller, which get updated as the user drags the tool.
+use crate::sys::{self, ImPlotPoint, ImPlotRect, ImVec4};
+use crate::IMPLOT_AUTO_COL;
+use bitflags::bitflags;
+
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotDragToolFlags"))]
+bitflags! {
+    /// Flags for customizing drag tools. Documentation copied from implot.h for convenience.
+    #[repr(transparent)]
+    pub struct DragToolFlags: u32 {
+        /// "Default" according to original docs
+        const NONE = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_None;
+        /// Drag tools won't change cursor icons when hovered or held
+        const NO_CURSORS = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoCursors;
+        /// The drag tool won't be considered for plot fits
+        const NO_FIT = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoFit;
+        /// Lock the tool from user inputs
+        const NO_INPUTS = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_NoInputs;
+        /// Tool rendering will be delayed one frame; useful when applying position-constraints
+        const DELAYED = sys::ImPlotDragToolFlags__ImPlotDragToolFlags_Delayed;
+    }
+}
+
+/// Internal helper function to convert an optional color to what ImPlot expects, which is
+/// `IMPLOT_AUTO_COL` if no color was chosen.
+fn color_or_auto(color: Option<[f32; 4]>) -> ImVec4 {
+    color.map_or(IMPLOT_AUTO_COL, |[x, y, z, w]| ImVec4 { x, y, z, w })
+}
+
+/// Struct to provide functionality for a point that can be dragged around in a plot.
+pub struct DragPoint {
+    /// ID of the point, has to be unique among the drag tools of a plot
+    id: i32,
+    /// Color of the point, or `None` for the automatic color
+    color: Option<[f32; 4]>,
+    /// Size of the point, in pixels
+    size: f32,
+    /// Flags relating to the point
+    flags: DragToolFlags,
+}
+
+impl DragPoint {
+    /// Create a new draggable point. The ID has to be unique among the drag tools of a plot.
+    /// Does not draw anything yet.
+    pub fn new(id: i32) -> Self {
+        Self {
+            id,
+            color: None,
+            size: 4.0,
+            flags: DragToolFlags::NONE,
+        }
+    }
+
+    /// Set the color of the point, given as [red, green, blue, alpha] with components between
+    /// 0.0 and 1.0.
+    #[inline]
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the size of the point, in pixels. Defaults to 4.
+    #[inline]
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the flags for the point, see the help for `DragToolFlags` for what the available
+    /// flags are
+    #[inline]
+    pub fn with_flags(mut self, flags: &DragToolFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Draw the point at the given position, and update the position if the user drags the
+    /// point. Returns true if the position was changed this frame. Use this in closures passed
+    /// to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "DragPoint"))]
+    pub fn plot(&self, point: &mut ImPlotPoint) -> bool {
+        unsafe {
+            sys::ImPlot_DragPoint(
+                self.id,
+                &mut point.x as *mut f64,
+                &mut point.y as *mut f64,
+                color_or_auto(self.color),
+                self.size,
+                self.flags.bits() as sys::ImPlotDragToolFlags,
+            )
+        }
+    }
+}
+
+/// Struct to provide functionality for a vertical line (at a given x value) that can be dragged
+/// around in a plot.
+pub struct DragLineX {
+    /// ID of the line, has to be unique among the drag tools of a plot
+    id: i32,
+    /// Color of the line, or `None` for the automatic color
+    color: Option<[f32; 4]>,
+    /// Thickness of the line, in pixels
+    thickness: f32,
+    /// Flags relating to the line
+    flags: DragToolFlags,
+}
+
+impl DragLineX {
+    /// Create a new draggable vertical line. The ID has to be unique among the drag tools of a
+    /// plot. Does not draw anything yet.
+    pub fn new(id: i32) -> Self {
+        Self {
+            id,
+            color: None,
+            thickness: 1.0,
+            flags: DragToolFlags::NONE,
+        }
+    }
+
+    /// Set the color of the line, given as [red, green, blue, alpha] with components between
+    /// 0.0 and 1.0.
+    #[inline]
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the thickness of the line, in pixels. Defaults to 1.
+    #[inline]
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set the flags for the line, see the help for `DragToolFlags` for what the available
+    /// flags are
+    #[inline]
+    pub fn with_flags(mut self, flags: &DragToolFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Draw the line at the given x value, and update the value if the user drags the line.
+    /// Returns true if the value was changed this frame. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "DragLineX"))]
+    pub fn plot(&self, x: &mut f64) -> bool {
+        unsafe {
+            sys::ImPlot_DragLineX(
+                self.id,
+                x as *mut f64,
+                color_or_auto(self.color),
+                self.thickness,
+                self.flags.bits() as sys::ImPlotDragToolFlags,
+            )
+        }
+    }
+}
+
+/// Struct to provide functionality for a horizontal line (at a given y value) that can be
+/// dragged around in a plot.
+pub struct DragLineY {
+    /// ID of the line, has to be unique among the drag tools of a plot
+    id: i32,
+    /// Color of the line, or `None` for the automatic color
+    color: Option<[f32; 4]>,
+    /// Thickness of the line, in pixels
+    thickness: f32,
+    /// Flags relating to the line
+    flags: DragToolFlags,
+}
+
+impl DragLineY {
+    /// Create a new draggable horizontal line. The ID has to be unique among the drag tools of
+    /// a plot. Does not draw anything yet.
+    pub fn new(id: i32) -> Self {
+        Self {
+            id,
+            color: None,
+            thickness: 1.0,
+            flags: DragToolFlags::NONE,
+        }
+    }
+
+    /// Set the color of the line, given as [red, green, blue, alpha] with components between
+    /// 0.0 and 1.0.
+    #[inline]
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the thickness of the line, in pixels. Defaults to 1.
+    #[inline]
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness;
+        self
+    }
+
+    /// Set the flags for the line, see the help for `DragToolFlags` for what the available
+    /// flags are
+    #[inline]
+    pub fn with_flags(mut self, flags: &DragToolFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Draw the line at the given y value, and update the value if the user drags the line.
+    /// Returns true if the value was changed this frame. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "DragLineY"))]
+    pub fn plot(&self, y: &mut f64) -> bool {
+        unsafe {
+            sys::ImPlot_DragLineY(
+                self.id,
+                y as *mut f64,
+                color_or_auto(self.color),
+                self.thickness,
+                self.flags.bits() as sys::ImPlotDragToolFlags,
+            )
+        }
+    }
+}
+
+/// Struct to provide functionality for a rectangle that can be dragged around and resized in a
+/// plot. This is also what replaces the plot queries of earlier ImPlot versions - put a
+/// rectangle into the plot and read back its extents.
+pub struct DragRect {
+    /// ID of the rectangle, has to be unique among the drag tools of a plot
+    id: i32,
+    /// Color of the rectangle, or `None` for the automatic color
+    color: Option<[f32; 4]>,
+    /// Flags relating to the rectangle
+    flags: DragToolFlags,
+}
+
+impl DragRect {
+    /// Create a new draggable rectangle. The ID has to be unique among the drag tools of a
+    /// plot. Does not draw anything yet.
+    pub fn new(id: i32) -> Self {
+        Self {
+            id,
+            color: None,
+            flags: DragToolFlags::NONE,
+        }
+    }
+
+    /// Set the color of the rectangle, given as [red, green, blue, alpha] with components
+    /// between 0.0 and 1.0.
+    #[inline]
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the flags for the rectangle, see the help for `DragToolFlags` for what the available
+    /// flags are
+    #[inline]
+    pub fn with_flags(mut self, flags: &DragToolFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Draw the rectangle with the given extents, and update them if the user drags or resizes
+    /// the rectangle. Returns true if the extents were changed this frame. Use this in closures
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "DragRect"))]
+    pub fn plot(&self, rect: &mut ImPlotRect) -> bool {
+        unsafe {
+            sys::ImPlot_DragRect(
+                self.id,
+                &mut rect.X.Min as *mut f64,
+                &mut rect.Y.Min as *mut f64,
+                &mut rect.X.Max as *mut f64,
+                &mut rect.Y.Max as *mut f64,
+                color_or_auto(self.color),
+                self.flags.bits() as sys::ImPlotDragToolFlags,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::run_frames, Plot};
+
+    #[test]
+    fn test_drag_tools_keep_values_without_input() {
+        let mut point = ImPlotPoint { x: 0.5, y: 0.25 };
+        let mut x = 0.75;
+        let mut rect = ImPlotRect {
+            X: [0.1, 0.2].into(),
+            Y: [0.3, 0.4].into(),
+        };
+        run_frames(2, |_, plot_ui| {
+            Plot::new("Drag tools").build(plot_ui, || {
+                assert!(!DragPoint::new(0).plot(&mut point));
+                assert!(!DragLineX::new(1)
+                    .with_color([1.0, 0.0, 0.0, 1.0])
+                    .plot(&mut x));
+                assert!(!DragRect::new(2)
+                    .with_flags(&DragToolFlags::NO_FIT)
+                    .plot(&mut rect));
+            });
+        });
+        assert_eq!([point.x, point.y], [0.5, 0.25]);
+        assert_eq!(x, 0.75);
+        assert_eq!([rect.X.Min, rect.X.Max], [0.1, 0.2]);
+    }
+}
@@ -19,21 +19,43 @@
 use implot_sys as sys;
 
 // TODO(4bb4) facade-wrap these?
-pub use self::{context::*, plot::*, plot_elements::*};
+#[cfg(feature = "serde")]
+pub use self::theme::*;
+pub use self::{
+    axis::*, context::*, drag_tools::*, plot::*, plot_data::*, plot_elements::*, style::*,
+    subplots::*,
+};
+use std::cell::RefCell;
 use std::os::raw::c_char;
 pub use sys::{ImPlotPoint, ImPlotRange, ImPlotRect, ImVec2, ImVec4};
 
+mod axis;
 mod context;
+mod drag_tools;
 mod plot;
+mod plot_data;
 mod plot_elements;
+mod style;
+mod subplots;
+#[cfg(feature = "serde")]
+mod theme;
 
 // The bindings for some reason don't contain this - it has to match the IMPLOT_AUTO from
 // the original C++ header for things to work properly.
 const IMPLOT_AUTO: i32 = -1;
 
-// Number of Y axes, this is used in a bunch of places for storing things like settings.
-// If this changes, also change the YAxisChoice enum.
-const NUMBER_OF_Y_AXES: usize = 3;
+// Same for IMPLOT_AUTO_COL, which tells ImPlot to pick a color automatically (usually the next
+// color of the colormap).
+const IMPLOT_AUTO_COL: ImVec4 = ImVec4 {
+    x: 0.0,
+    y: 0.0,
+    z: 0.0,
+    w: -1.0,
+};
+
+// Number of axes in total (X1-X3 and Y1-Y3), used for storing per-axis settings in arrays
+// indexed by `Axis`. If this changes, also change the Axis enum.
+const NUMBER_OF_AXES: usize = sys::ImAxis__ImAxis_COUNT as usize;
 
 /// Choice of Y axis. This an enum instead of just an integer so as to make it impossible
 /// to select a Y axis that is not present - this makes it easier to avoid `Result`-type
@@ -50,9 +72,24 @@ pub enum YAxisChoice {
     Third = sys::ImAxis__ImAxis_Y3,
 }
 
-#[rustversion::attr(since(1.48), doc(alias = "ImAxis"))]
+/// Choice of X axis. Like [`YAxisChoice`], this makes it impossible to select an X axis that
+/// does not exist.
+// Implementation note: Like YAxisChoice, this is converted straight to the ImAxis index.
 #[derive(Clone)]
 #[repr(u32)]
+pub enum XAxisChoice {
+    First = sys::ImAxis__ImAxis_X1,
+    Second = sys::ImAxis__ImAxis_X2,
+    Third = sys::ImAxis__ImAxis_X3,
+}
+
+/// Choice of axis, for functionality that applies to both X and Y axes. ImPlot has three of
+/// each, where X1 and Y1 are always enabled and the others are enabled on demand.
+// Implementation note: Like YAxisChoice, this is converted straight to an usize index for
+// storing per-axis data in arrays, so the values have to stay in the 0..NUMBER_OF_AXES range.
+#[rustversion::attr(since(1.48), doc(alias = "ImAxis"))]
+#[derive(Clone, Copy, Debug)]
+#[repr(u32)]
 pub enum Axis {
     X1 = sys::ImAxis__ImAxis_X1,
     X2 = sys::ImAxis__ImAxis_X2,
@@ -62,6 +99,34 @@ pub enum Axis {
     Y3 = sys::ImAxis__ImAxis_Y3,
 }
 
+impl From<XAxisChoice> for Axis {
+    fn from(x_axis_choice: XAxisChoice) -> Self {
+        match x_axis_choice {
+            XAxisChoice::First => Axis::X1,
+            XAxisChoice::Second => Axis::X2,
+            XAxisChoice::Third => Axis::X3,
+        }
+    }
+}
+
+impl From<YAxisChoice> for Axis {
+    fn from(y_axis_choice: YAxisChoice) -> Self {
+        match y_axis_choice {
+            YAxisChoice::First => Axis::Y1,
+            YAxisChoice::Second => Axis::Y2,
+            YAxisChoice::Third => Axis::Y3,
+        }
+    }
+}
+
+/// Turn an Option<XAxisChoice> into an i32. Picks IMPLOT_AUTO for None.
+fn x_axis_choice_option_to_i32(x_axis_choice: Option<XAxisChoice>) -> i32 {
+    match x_axis_choice {
+        Some(choice) => choice as i32,
+        None => IMPLOT_AUTO,
+    }
+}
+
 /// Turn an Option<YAxisChoice> into an i32. Picks IMPLOT_AUTO for None.
 #[rustversion::attr(since(1.48), doc(alias = "IMPLOT_AUTO"))]
 fn y_axis_choice_option_to_i32(y_axis_choice: Option<YAxisChoice>) -> i32 {
@@ -71,18 +136,34 @@ fn y_axis_choice_option_to_i32(y_axis_choice: Option<YAxisChoice>) -> i32 {
     }
 }
 
-/// A temporary reference for building plots. This does not really do anything on its own at
-/// this point, but it is used to enforce that a context is created and active for other features,
-/// such as creating plots.
+/// A temporary reference for building plots, obtained once per frame from the context. It is
+/// used to enforce that a context is created and active for other features, such as creating
+/// plots, and it keeps track of the style variables and colors pushed during the frame.
 pub struct PlotUi<'ui> {
     context: &'ui Context,
+    /// Style variables currently pushed through this struct, in push order
+    style_vars: RefCell<Vec<StyleVar>>,
+    /// Style colors currently pushed through this struct, in push order
+    style_colors: RefCell<Vec<PlotColorElement>>,
+}
+
+impl<'ui> PlotUi<'ui> {
+    /// Internal helper function to create a plot ui with empty style stacks
+    pub(crate) fn new(context: &'ui Context) -> Self {
+        Self {
+            context,
+            style_vars: RefCell::new(Vec::new()),
+            style_colors: RefCell::new(Vec::new()),
+        }
+    }
 }
 
 // --- Markers, color maps, style variables, legend location ----------------------------------
 /// Markers, documentation copied from implot.h for convenience.
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotMarker"))]
 #[repr(i32)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Marker {
     /// no marker
     None = sys::ImPlotMarker__ImPlotMarker_None,
@@ -108,6 +189,29 @@ pub enum Marker {
     Asterisk = sys::ImPlotMarker__ImPlotMarker_Asterisk,
 }
 
+impl Marker {
+    /// Internal helper function to convert a raw marker value from ImPlot, returning `None`
+    /// for values that don't correspond to a marker.
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [
+            Marker::None,
+            Marker::Circle,
+            Marker::Square,
+            Marker::Diamond,
+            Marker::Up,
+            Marker::Down,
+            Marker::Left,
+            Marker::Right,
+            Marker::Cross,
+            Marker::Plus,
+            Marker::Asterisk,
+        ]
+        .iter()
+        .copied()
+        .find(|marker| *marker as i32 == raw)
+    }
+}
+
 /// Colorable plot elements. These are called "ImPlotCol" in ImPlot itself, but I found that
 /// name somewhat confusing because we are not referring to colors, but _which_ thing can
 /// be colored - hence I added the "Element".
@@ -141,6 +245,18 @@ pub enum PlotColorElement {
     TitleText = sys::ImPlotCol__ImPlotCol_TitleText,
     /// Color of text appearing inside of plots (defaults to ImGuiCol_Text)
     InlayText = sys::ImPlotCol__ImPlotCol_InlayText,
+    /// Axis label and tick label color (defaults to ImGuiCol_Text)
+    AxisText = sys::ImPlotCol__ImPlotCol_AxisText,
+    /// Axis grid color (defaults to 25% ImPlotCol_AxisText)
+    AxisGrid = sys::ImPlotCol__ImPlotCol_AxisGrid,
+    /// Axis tick color (defaults to AxisGrid)
+    AxisTick = sys::ImPlotCol__ImPlotCol_AxisTick,
+    /// Background color of axis hover region (defaults to transparent)
+    AxisBg = sys::ImPlotCol__ImPlotCol_AxisBg,
+    /// Axis hover color (defaults to ImGuiCol_ButtonHovered)
+    AxisBgHovered = sys::ImPlotCol__ImPlotCol_AxisBgHovered,
+    /// Axis active color (defaults to ImGuiCol_ButtonActive)
+    AxisBgActive = sys::ImPlotCol__ImPlotCol_AxisBgActive,
     /// Box-selection color (defaults to yellow)
     Selection = sys::ImPlotCol__ImPlotCol_Selection,
     /// crosshairs color (defaults to ImPlotCol_PlotBorder)
@@ -150,7 +266,8 @@ pub enum PlotColorElement {
 /// Colormap choice. Documentation copied from implot.h for convenience.
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotColormap"))]
 #[repr(u32)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Colormap {
     /// a.k.a. seaborn deep (n=10)
     Deep = sys::ImPlotColormap__ImPlotColormap_Deep,
@@ -172,68 +289,186 @@ pub enum Colormap {
     Pink = sys::ImPlotColormap__ImPlotColormap_Pink,
     /// a.k.a. MATLAB "jet" (n=11)
     Jet = sys::ImPlotColormap__ImPlotColormap_Jet,
+    /// a.k.a. matplotlib "twilight" (n=11)
+    Twilight = sys::ImPlotColormap__ImPlotColormap_Twilight,
+    /// red/blue, Color Brewer (n=11)
+    RdBu = sys::ImPlotColormap__ImPlotColormap_RdBu,
+    /// brown/blue-green, Color Brewer (n=11)
+    BrBG = sys::ImPlotColormap__ImPlotColormap_BrBG,
+    /// pink/yellow-green, Color Brewer (n=11)
+    PiYG = sys::ImPlotColormap__ImPlotColormap_PiYG,
+    /// color spectrum, Color Brewer (n=11)
+    Spectral = sys::ImPlotColormap__ImPlotColormap_Spectral,
+    /// white/black (n=2)
+    Greys = sys::ImPlotColormap__ImPlotColormap_Greys,
+}
+
+impl Colormap {
+    /// Internal helper function to convert a raw colormap index from ImPlot, returning `None`
+    /// for indices that don't correspond to one of the presets.
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [
+            Colormap::Deep,
+            Colormap::Dark,
+            Colormap::Pastel,
+            Colormap::Paired,
+            Colormap::Viridis,
+            Colormap::Plasma,
+            Colormap::Hot,
+            Colormap::Cool,
+            Colormap::Pink,
+            Colormap::Jet,
+            Colormap::Twilight,
+            Colormap::RdBu,
+            Colormap::BrBG,
+            Colormap::PiYG,
+            Colormap::Spectral,
+            Colormap::Greys,
+        ]
+        .iter()
+        .copied()
+        .find(|colormap| *colormap as i32 == raw)
+    }
 }
 
-/// Style variable choice, as in "which thing will be affected by a style setting".
+/// Style variable to push, together with the value it is pushed with. Each variable carries
+/// a value of the type ImPlot expects for it, see [`PlotUi::push_style_var`].
 #[rustversion::attr(since(1.48), doc(alias = "ImPlotStyleVar"))]
-#[repr(u32)]
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum StyleVar {
-    /// f32, line weight in pixels
-    LineWeight = sys::ImPlotStyleVar__ImPlotStyleVar_LineWeight,
-    /// u32,  marker specification
-    Marker = sys::ImPlotStyleVar__ImPlotStyleVar_Marker,
-    /// f32, marker size in pixels (roughly the marker's "radius")
-    MarkerSize = sys::ImPlotStyleVar__ImPlotStyleVar_MarkerSize,
-    /// f32, outline weight of markers in pixels
-    MarkerWeight = sys::ImPlotStyleVar__ImPlotStyleVar_MarkerWeight,
-    /// f32, alpha modifier applied to all plot item fills
-    FillAlpha = sys::ImPlotStyleVar__ImPlotStyleVar_FillAlpha,
-    /// f32, error bar whisker width in pixels
-    ErrorBarSize = sys::ImPlotStyleVar__ImPlotStyleVar_ErrorBarSize,
-    /// f32, error bar whisker weight in pixels
-    ErrorBarWeight = sys::ImPlotStyleVar__ImPlotStyleVar_ErrorBarWeight,
-    /// f32, digital channels bit height (at 1) in pixels
-    DigitalBitHeight = sys::ImPlotStyleVar__ImPlotStyleVar_DigitalBitHeight,
-    /// f32, digital channels bit padding gap in pixels
-    DigitalBitGap = sys::ImPlotStyleVar__ImPlotStyleVar_DigitalBitGap,
-    /// f32,  thickness of border around plot area
-    PlotBorderSize = sys::ImPlotStyleVar__ImPlotStyleVar_PlotBorderSize,
-    /// f32,  alpha multiplier applied to minor axis grid lines
-    MinorAlpha = sys::ImPlotStyleVar__ImPlotStyleVar_MinorAlpha,
-    /// ImVec2, major tick lengths for X and Y axes
-    MajorTickLen = sys::ImPlotStyleVar__ImPlotStyleVar_MajorTickLen,
-    /// ImVec2, minor tick lengths for X and Y axes
-    MinorTickLen = sys::ImPlotStyleVar__ImPlotStyleVar_MinorTickLen,
-    /// ImVec2, line thickness of major ticks
-    MajorTickSize = sys::ImPlotStyleVar__ImPlotStyleVar_MajorTickSize,
-    /// ImVec2, line thickness of minor ticks
-    MinorTickSize = sys::ImPlotStyleVar__ImPlotStyleVar_MinorTickSize,
-    /// ImVec2, line thickness of major grid lines
-    MajorGridSize = sys::ImPlotStyleVar__ImPlotStyleVar_MajorGridSize,
-    /// ImVec2, line thickness of minor grid lines
-    MinorGridSize = sys::ImPlotStyleVar__ImPlotStyleVar_MinorGridSize,
-    /// ImVec2, padding between widget frame and plot area and/or labels
-    PlotPadding = sys::ImPlotStyleVar__ImPlotStyleVar_PlotPadding,
-    /// ImVec2, padding between axes labels, tick labels, and plot edge
-    LabelPadding = sys::ImPlotStyleVar__ImPlotStyleVar_LabelPadding,
-    /// ImVec2, legend padding from top-left of plot
-    LegendPadding = sys::ImPlotStyleVar__ImPlotStyleVar_LegendPadding,
-    /// ImVec2, legend inner padding from legend edges
-    LegendInnerPadding = sys::ImPlotStyleVar__ImPlotStyleVar_LegendInnerPadding,
-    /// ImVec2, spacing between legend entries
-    LegendSpacing = sys::ImPlotStyleVar__ImPlotStyleVar_LegendSpacing,
-    /// ImVec2, padding between plot edge and interior info text
-    MousePosPadding = sys::ImPlotStyleVar__ImPlotStyleVar_MousePosPadding,
-    /// ImVec2, text padding around annotation labels
-    AnnotationPadding = sys::ImPlotStyleVar__ImPlotStyleVar_AnnotationPadding,
-    /// ImVec2, additional fit padding as a percentage of the fit extents
-    /// (e.g. ImVec2(0.1f,0.1f) adds 10% to the fit extents of X and Y)
-    FitPadding = sys::ImPlotStyleVar__ImPlotStyleVar_FitPadding,
-    /// ImVec2, default size used when ImVec2(0,0) is passed to BeginPlot
-    PlotDefaultSize = sys::ImPlotStyleVar__ImPlotStyleVar_PlotDefaultSize,
-    /// ImVec2, minimum size plot frame can be when shrunk
-    PlotMinSize = sys::ImPlotStyleVar__ImPlotStyleVar_PlotMinSize,
+    /// Line weight in pixels
+    LineWeight(f32),
+    /// Marker specification
+    Marker(Marker),
+    /// Marker size in pixels (roughly the marker's "radius")
+    MarkerSize(f32),
+    /// Outline weight of markers in pixels
+    MarkerWeight(f32),
+    /// Alpha modifier applied to all plot item fills
+    FillAlpha(f32),
+    /// Error bar whisker width in pixels
+    ErrorBarSize(f32),
+    /// Error bar whisker weight in pixels
+    ErrorBarWeight(f32),
+    /// Digital channels bit height (at 1) in pixels
+    DigitalBitHeight(f32),
+    /// Digital channels bit padding gap in pixels
+    DigitalBitGap(f32),
+    /// Thickness of border around plot area
+    PlotBorderSize(f32),
+    /// Alpha multiplier applied to minor axis grid lines
+    MinorAlpha(f32),
+    /// Major tick lengths for X and Y axes
+    MajorTickLen([f32; 2]),
+    /// Minor tick lengths for X and Y axes
+    MinorTickLen([f32; 2]),
+    /// Line thickness of major ticks
+    MajorTickSize([f32; 2]),
+    /// Line thickness of minor ticks
+    MinorTickSize([f32; 2]),
+    /// Line thickness of major grid lines
+    MajorGridSize([f32; 2]),
+    /// Line thickness of minor grid lines
+    MinorGridSize([f32; 2]),
+    /// Padding between widget frame and plot area and/or labels
+    PlotPadding([f32; 2]),
+    /// Padding between axes labels, tick labels, and plot edge
+    LabelPadding([f32; 2]),
+    /// Legend padding from top-left of plot
+    LegendPadding([f32; 2]),
+    /// Legend inner padding from legend edges
+    LegendInnerPadding([f32; 2]),
+    /// Spacing between legend entries
+    LegendSpacing([f32; 2]),
+    /// Padding between plot edge and interior info text
+    MousePosPadding([f32; 2]),
+    /// Text padding around annotation labels
+    AnnotationPadding([f32; 2]),
+    /// Additional fit padding as a percentage of the fit extents
+    /// (e.g. [0.1, 0.1] adds 10% to the fit extents of X and Y)
+    FitPadding([f32; 2]),
+    /// Default size used when [0, 0] is passed to BeginPlot
+    PlotDefaultSize([f32; 2]),
+    /// Minimum size plot frame can be when shrunk
+    PlotMinSize([f32; 2]),
+}
+
+impl StyleVar {
+    /// Internal helper function to get the ImPlot index of the variable
+    fn index(&self) -> sys::ImPlotStyleVar {
+        let index = match self {
+            StyleVar::LineWeight(_) => sys::ImPlotStyleVar__ImPlotStyleVar_LineWeight,
+            StyleVar::Marker(_) => sys::ImPlotStyleVar__ImPlotStyleVar_Marker,
+            StyleVar::MarkerSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MarkerSize,
+            StyleVar::MarkerWeight(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MarkerWeight,
+            StyleVar::FillAlpha(_) => sys::ImPlotStyleVar__ImPlotStyleVar_FillAlpha,
+            StyleVar::ErrorBarSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_ErrorBarSize,
+            StyleVar::ErrorBarWeight(_) => sys::ImPlotStyleVar__ImPlotStyleVar_ErrorBarWeight,
+            StyleVar::DigitalBitHeight(_) => sys::ImPlotStyleVar__ImPlotStyleVar_DigitalBitHeight,
+            StyleVar::DigitalBitGap(_) => sys::ImPlotStyleVar__ImPlotStyleVar_DigitalBitGap,
+            StyleVar::PlotBorderSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_PlotBorderSize,
+            StyleVar::MinorAlpha(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MinorAlpha,
+            StyleVar::MajorTickLen(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MajorTickLen,
+            StyleVar::MinorTickLen(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MinorTickLen,
+            StyleVar::MajorTickSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MajorTickSize,
+            StyleVar::MinorTickSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MinorTickSize,
+            StyleVar::MajorGridSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MajorGridSize,
+            StyleVar::MinorGridSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MinorGridSize,
+            StyleVar::PlotPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_PlotPadding,
+            StyleVar::LabelPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_LabelPadding,
+            StyleVar::LegendPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_LegendPadding,
+            StyleVar::LegendInnerPadding(_) => {
+                sys::ImPlotStyleVar__ImPlotStyleVar_LegendInnerPadding
+            }
+            StyleVar::LegendSpacing(_) => sys::ImPlotStyleVar__ImPlotStyleVar_LegendSpacing,
+            StyleVar::MousePosPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_MousePosPadding,
+            StyleVar::AnnotationPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_AnnotationPadding,
+            StyleVar::FitPadding(_) => sys::ImPlotStyleVar__ImPlotStyleVar_FitPadding,
+            StyleVar::PlotDefaultSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_PlotDefaultSize,
+            StyleVar::PlotMinSize(_) => sys::ImPlotStyleVar__ImPlotStyleVar_PlotMinSize,
+        };
+        // "as" casts saturate as of Rust 1.45. This is safe here, the enum values are small.
+        index as sys::ImPlotStyleVar
+    }
+
+    /// Internal helper function to push the variable and its value to ImPlot's style variable
+    /// stack
+    fn push(&self) {
+        let index = self.index();
+        unsafe {
+            match *self {
+                StyleVar::LineWeight(value)
+                | StyleVar::MarkerSize(value)
+                | StyleVar::MarkerWeight(value)
+                | StyleVar::FillAlpha(value)
+                | StyleVar::ErrorBarSize(value)
+                | StyleVar::ErrorBarWeight(value)
+                | StyleVar::DigitalBitHeight(value)
+                | StyleVar::DigitalBitGap(value)
+                | StyleVar::PlotBorderSize(value)
+                | StyleVar::MinorAlpha(value) => sys::ImPlot_PushStyleVar_Float(index, value),
+                StyleVar::Marker(marker) => sys::ImPlot_PushStyleVar_Int(index, marker as i32),
+                StyleVar::MajorTickLen([x, y])
+                | StyleVar::MinorTickLen([x, y])
+                | StyleVar::MajorTickSize([x, y])
+                | StyleVar::MinorTickSize([x, y])
+                | StyleVar::MajorGridSize([x, y])
+                | StyleVar::MinorGridSize([x, y])
+                | StyleVar::PlotPadding([x, y])
+                | StyleVar::LabelPadding([x, y])
+                | StyleVar::LegendPadding([x, y])
+                | StyleVar::LegendInnerPadding([x, y])
+                | StyleVar::LegendSpacing([x, y])
+                | StyleVar::MousePosPadding([x, y])
+                | StyleVar::AnnotationPadding([x, y])
+                | StyleVar::FitPadding([x, y])
+                | StyleVar::PlotDefaultSize([x, y])
+                | StyleVar::PlotMinSize([x, y]) => {
+                    sys::ImPlot_PushStyleVar_Vec2(index, ImVec2 { x, y })
+                }
+            }
+        }
+    }
 }
 
 /// Used to position items on a plot (e.g. legends, labels, etc.)
@@ -276,113 +511,198 @@ pub fn push_colormap(preset: Colormap) {
 // TODO(eiz): AddColormap
 
 // --- Push/pop utils -------------------------------------------------------------------------
-// Currently not in a struct yet. imgui-rs has some smarts about dealing with stacks, in particular
-// leak detection, which I'd like to replicate here at some point.
-/// Push a style color to the stack, giving an element and the four components of the color.
-/// The components should be between 0.0 (no intensity) and 1.0 (full intensity).
-/// The return value is a token that gets used for removing the style color from the stack again:
-/// ```no_run
-/// # use implot::{push_style_color, PlotColorElement};
-/// let pushed_var = push_style_color(&PlotColorElement::Line, 1.0, 1.0, 1.0, 0.2);
-/// // Plot some things
-/// pushed_var.pop();
-/// ```
-#[rustversion::attr(since(1.48), doc(alias = "PushStyleColor"))]
-pub fn push_style_color(
-    element: &PlotColorElement,
-    red: f32,
-    green: f32,
-    blue: f32,
-    alpha: f32,
-) -> StyleColorToken {
-    unsafe {
-        sys::ImPlot_PushStyleColor_Vec4(
-            *element as sys::ImPlotCol,
-            sys::ImVec4 {
-                x: red,
-                y: green,
-                z: blue,
-                w: alpha,
-            },
-        );
+// Like in imgui-rs, pushing returns a token that pops the change again when it is dropped. The
+// plot ui keeps track of what is pushed, which is used to check that tokens are popped in
+// reverse push order, and that nothing is left on the stacks at the end of the frame.
+
+impl<'ui> PlotUi<'ui> {
+    /// Push a style color to the stack, giving an element and the color as [red, green, blue,
+    /// alpha], with components between 0.0 (no intensity) and 1.0 (full intensity). The color
+    /// is popped again when the returned token is dropped, or when it is popped explicitly:
+    /// ```no_run
+    /// # use implot::{PlotColorElement, PlotUi};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// let pushed_color = plot_ui.push_style_color(&PlotColorElement::Line, [1.0, 1.0, 1.0, 0.2]);
+    /// // Plot some things
+    /// pushed_color.pop();
+    /// # }
+    /// ```
+    #[rustversion::attr(since(1.48), doc(alias = "PushStyleColor"))]
+    pub fn push_style_color(
+        &self,
+        element: &PlotColorElement,
+        [x, y, z, w]: [f32; 4],
+    ) -> StyleColorToken<'_> {
+        unsafe {
+            sys::ImPlot_PushStyleColor_Vec4(*element as sys::ImPlotCol, sys::ImVec4 { x, y, z, w });
+        }
+        StyleColorToken {
+            position: push_to_stack(&self.style_colors, *element),
+            stack: &self.style_colors,
+        }
+    }
+
+    /// Push a style variable to the stack, together with its value. The variable is popped
+    /// again when the returned token is dropped, or when it is popped explicitly:
+    /// ```no_run
+    /// # use implot::{Marker, PlotUi, StyleVar};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// let pushed_var = plot_ui.push_style_var(StyleVar::LineWeight(11.0));
+    /// let marker_choice = plot_ui.push_style_var(StyleVar::Marker(Marker::Cross));
+    /// // Plot some things
+    /// marker_choice.pop();
+    /// pushed_var.pop();
+    /// # }
+    /// ```
+    #[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
+    pub fn push_style_var(&self, var: StyleVar) -> StyleVarToken<'_> {
+        var.push();
+        StyleVarToken {
+            position: push_to_stack(&self.style_vars, var),
+            stack: &self.style_vars,
+        }
+    }
+
+    /// Run `f` with the given style color pushed, and pop it again afterwards. The color is
+    /// given as [red, green, blue, alpha].
+    /// ```no_run
+    /// # use implot::{PlotColorElement, PlotLine, PlotUi};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// plot_ui.with_style_color(&PlotColorElement::Line, [1.0, 0.0, 0.0, 1.0], || {
+    ///     PlotLine::new("red line").plot(&[0.0, 1.0], &[0.0, 1.0]);
+    /// });
+    /// # }
+    /// ```
+    pub fn with_style_color<R, F: FnOnce() -> R>(
+        &self,
+        element: &PlotColorElement,
+        color: [f32; 4],
+        f: F,
+    ) -> R {
+        let _token = self.push_style_color(element, color);
+        f()
     }
-    StyleColorToken { was_popped: false }
-}
 
-/// Tracks a change pushed to the style color stack
-pub struct StyleColorToken {
-    /// Whether this token has been popped or not.
-    was_popped: bool,
+    /// Run `f` with the given style variable pushed, and pop it again afterwards.
+    /// ```no_run
+    /// # use implot::{PlotLine, PlotUi, StyleVar};
+    /// # fn example(plot_ui: &PlotUi) {
+    /// plot_ui.with_style_var(StyleVar::LineWeight(3.0), || {
+    ///     PlotLine::new("thick line").plot(&[0.0, 1.0], &[0.0, 1.0]);
+    /// });
+    /// # }
+    /// ```
+    pub fn with_style_var<R, F: FnOnce() -> R>(&self, var: StyleVar, f: F) -> R {
+        let _token = self.push_style_var(var);
+        f()
+    }
 }
 
-impl StyleColorToken {
-    #[rustversion::attr(since(1.48), doc(alias = "PopStyleColor"))]
-    pub fn pop(mut self) {
-        if self.was_popped {
-            panic!("Attempted to pop a style color token twice.")
+impl Drop for PlotUi<'_> {
+    /// Check that the style stacks are balanced at the end of the frame. Tokens can't outlive
+    /// the plot ui, so anything left here was leaked with `std::mem::forget` or similar. The
+    /// leftovers are popped to keep ImPlot's stacks balanced, and reported in debug builds.
+    fn drop(&mut self) {
+        let style_vars = self.style_vars.get_mut();
+        let style_colors = self.style_colors.get_mut();
+        if style_vars.is_empty() && style_colors.is_empty() {
+            return;
         }
-        self.was_popped = true;
         unsafe {
-            sys::ImPlot_PopStyleColor(1);
+            // "as" casts saturate as of Rust 1.45. This is safe here, the stacks are small.
+            sys::ImPlot_PopStyleVar(style_vars.len() as i32);
+            sys::ImPlot_PopStyleColor(style_colors.len() as i32);
+        }
+        let message = format!(
+            "Unbalanced style stacks at the end of the frame, these were pushed but never \
+             popped: style variables {:?}, style colors {:?}",
+            style_vars, style_colors
+        );
+        style_vars.clear();
+        style_colors.clear();
+        if cfg!(debug_assertions) && !std::thread::panicking() {
+            panic!("{}", message);
         }
     }
 }
 
-/// Push a f32 style variable to the stack. The returned token is used for removing
-/// the variable from the stack again:
-/// ```no_run
-/// # use implot::{push_style_var_f32, StyleVar};
-/// let pushed_var = push_style_var_f32(&StyleVar::LineWeight, 11.0);
-/// // Plot some things
-/// pushed_var.pop();
-/// ```
-#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
-pub fn push_style_var_f32(element: &StyleVar, value: f32) -> StyleVarToken {
-    unsafe {
-        sys::ImPlot_PushStyleVar_Float(*element as sys::ImPlotStyleVar, value);
-    }
-    StyleVarToken { was_popped: false }
+/// Internal helper function to record a push on one of the tracked style stacks. Returns the
+/// position of the pushed entry.
+fn push_to_stack<T>(stack: &RefCell<Vec<T>>, entry: T) -> usize {
+    let mut stack = stack.borrow_mut();
+    stack.push(entry);
+    stack.len() - 1
 }
 
-/// Push an u32 style variable to the stack. The only i32 style variable is Marker
-/// at the moment, for that, use something like
-/// ```no_run
-/// # use implot::{push_style_var_i32, StyleVar, Marker};
-/// let markerchoice = push_style_var_i32(&StyleVar::Marker, Marker::Cross as i32);
-/// // plot things
-/// markerchoice.pop()
-/// ```
-#[rustversion::attr(since(1.48), doc(alias = "PushStyleVar"))]
-pub fn push_style_var_i32(element: &StyleVar, value: i32) -> StyleVarToken {
-    unsafe {
-        sys::ImPlot_PushStyleVar_Int(*element as sys::ImPlotStyleVar, value);
+/// Internal helper function to record a pop from one of the tracked style stacks. ImPlot always
+/// pops the most recently pushed entry, so popping anything else is a mistake, which is checked
+/// in debug builds. Tokens are popped while unwinding as well, where panicking again would abort,
+/// so the check is skipped then.
+fn pop_from_stack<T: std::fmt::Debug>(stack: &RefCell<Vec<T>>, position: usize, kind: &str) {
+    let mut stack = stack.borrow_mut();
+    debug_assert!(
+        std::thread::panicking() || stack.len() == position + 1,
+        "Style {} popped out of order, these were pushed after it and are still active: {:?}",
+        kind,
+        stack.get(position + 1..).unwrap_or(&[]),
+    );
+    stack.pop();
+}
+
+/// Tracks a change pushed to the style color stack. The change is popped when this is dropped.
+/// Tokens have to be popped in reverse push order, which is checked in debug builds. Release
+/// builds don't check this and pop ImPlot's most recently pushed color, which is not necessarily
+/// the one of this token.
+#[must_use = "the style color is popped again immediately if the token is not kept"]
+pub struct StyleColorToken<'ui> {
+    /// Stack of pushed colors in the plot ui this was pushed through
+    stack: &'ui RefCell<Vec<PlotColorElement>>,
+    /// Position of this change on the stack
+    position: usize,
+}
+
+impl StyleColorToken<'_> {
+    /// Pop this token from the stack. This is the same as dropping it, but makes the end of
+    /// the change visible in the code.
+    #[rustversion::attr(since(1.48), doc(alias = "PopStyleColor"))]
+    pub fn pop(self) {
+        drop(self);
     }
-    StyleVarToken { was_popped: false }
 }
 
-/// Push an ImVec2 style variable to the stack. The returned token is used for removing
-/// the variable from the stack again.
-pub fn push_style_var_imvec2(element: &StyleVar, value: ImVec2) -> StyleVarToken {
-    unsafe {
-        sys::ImPlot_PushStyleVar_Vec2(*element as sys::ImPlotStyleVar, value);
+impl Drop for StyleColorToken<'_> {
+    fn drop(&mut self) {
+        pop_from_stack(self.stack, self.position, "color");
+        unsafe {
+            sys::ImPlot_PopStyleColor(1);
+        }
     }
-    StyleVarToken { was_popped: false }
 }
 
-/// Tracks a change pushed to the style variable stack
-pub struct StyleVarToken {
-    /// Whether this token has been popped or not.
-    was_popped: bool,
+/// Tracks a change pushed to the style variable stack. The change is popped when this is
+/// dropped. Tokens have to be popped in reverse push order, which is checked in debug builds.
+/// Release builds don't check this and pop ImPlot's most recently pushed variable, which is not
+/// necessarily the one of this token.
+#[must_use = "the style variable is popped again immediately if the token is not kept"]
+pub struct StyleVarToken<'ui> {
+    /// Stack of pushed variables in the plot ui this was pushed through
+    stack: &'ui RefCell<Vec<StyleVar>>,
+    /// Position of this change on the stack
+    position: usize,
 }
 
-impl StyleVarToken {
-    /// Pop this token from the stack.
+impl StyleVarToken<'_> {
+    /// Pop this token from the stack. This is the same as dropping it, but makes the end of
+    /// the change visible in the code.
     #[rustversion::attr(since(1.48), doc(alias = "PopStyleVar"))]
-    pub fn pop(mut self) {
-        if self.was_popped {
-            panic!("Attempted to pop a style var token twice.")
-        }
-        self.was_popped = true;
+    pub fn pop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for StyleVarToken<'_> {
+    fn drop(&mut self) {
+        pop_from_stack(self.stack, self.position, "variable");
         unsafe {
             sys::ImPlot_PopStyleVar(1);
         }
@@ -396,125 +716,125 @@ pub fn is_plot_hovered() -> bool {
     unsafe { sys::ImPlot_IsPlotHovered() }
 }
 
-// TODO(eiz): DragRect
-
 /// Returns the mouse position in x,y coordinates of the current or most recent plot,
-/// for the specified choice of Y axis. If `None` is the Y axis choice, that means the
-/// most recently selected Y axis is chosen.
+/// for the specified choice of X and Y axes. If `None` is the choice for an axis, that means
+/// the most recently selected axis is chosen.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotMousePos"))]
-pub fn get_plot_mouse_position(y_axis_choice: Option<YAxisChoice>) -> ImPlotPoint {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
+pub fn get_plot_mouse_position(
+    x_axis_choice: Option<XAxisChoice>,
+    y_axis_choice: Option<YAxisChoice>,
+) -> ImPlotPoint {
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
     unsafe {
         sys::ImPlot_GetPlotMousePos(
             &mut point as *mut ImPlotPoint,
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         );
     }
     point
 }
 
 /// Convert pixels, given as an `ImVec2`, to a position in the current plot's coordinate system.
-/// Uses the specified Y axis, if any, otherwise whatever was previously chosen.
+/// Uses the specified X and Y axes, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
 pub fn pixels_to_plot_vec2(
     pixel_position: &ImVec2,
+    x_axis_choice: Option<XAxisChoice>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImPlotPoint {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
     unsafe {
         sys::ImPlot_PixelsToPlot_Vec2(
             &mut point as *mut ImPlotPoint,
             *pixel_position,
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         );
     }
     point
 }
 
 /// Convert pixels, given as floats `x` and `y`, to a position in the current plot's coordinate
-/// system. Uses the specified Y axis, if any, otherwise whatever was previously chosen.
+/// system. Uses the specified X and Y axes, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PixelsToPlot"))]
 pub fn pixels_to_plot_f32(
     pixel_position_x: f32,
     pixel_position_y: f32,
+    x_axis_choice: Option<XAxisChoice>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImPlotPoint {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut point = ImPlotPoint { x: 0.0, y: 0.0 }; // doesn't seem to have default()
     unsafe {
         sys::ImPlot_PixelsToPlot_Float(
             &mut point as *mut ImPlotPoint,
             pixel_position_x,
             pixel_position_y,
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         );
     }
     point
 }
 
-/// Convert a position in the current plot's coordinate system to pixels. Uses the specified Y
-/// axis, if any, otherwise whatever was previously chosen.
-///
+/// Convert a position in the current plot's coordinate system to pixels. Uses the specified X
+/// and Y axes, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
 pub fn plot_to_pixels_vec2(
     plot_position: &ImPlotPoint,
+    x_axis_choice: Option<XAxisChoice>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImVec2 {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut pixel_position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
     unsafe {
         sys::ImPlot_PlotToPixels_PlotPoInt(
             &mut pixel_position as *mut ImVec2,
             *plot_position,
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         );
     }
     pixel_position
 }
 
-/// Convert a position in the current plot's coordinate system to pixels. Uses the specified Y
-/// axis, if any, otherwise whatever was previously chosen.
+/// Convert a position in the current plot's coordinate system to pixels. Uses the specified X
+/// and Y axes, if any, otherwise whatever was previously chosen.
 #[rustversion::attr(since(1.48), doc(alias = "PlotToPixels"))]
 pub fn plot_to_pixels_f32(
     plot_position_x: f64,
     plot_position_y: f64,
+    x_axis_choice: Option<XAxisChoice>,
     y_axis_choice: Option<YAxisChoice>,
 ) -> ImVec2 {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
     let mut pixel_position = ImVec2 { x: 0.0, y: 0.0 }; // doesn't seem to have default()
     unsafe {
         sys::ImPlot_PlotToPixels_double(
             &mut pixel_position as *mut ImVec2,
             plot_position_x,
             plot_position_y,
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         );
     }
     pixel_position
 }
 
-/// Returns the current or most recent plot axis range for the specified choice of Y axis. If
-/// `None` is the Y axis choice, that means the most recently selected Y axis is chosen.
+/// Returns the current or most recent plot axis range for the specified choice of X and Y
+/// axes. If `None` is the choice for an axis, that means the most recently selected axis is
+/// chosen.
 #[rustversion::attr(since(1.48), doc(alias = "GetPlotLimits"))]
-pub fn get_plot_limits(y_axis_choice: Option<YAxisChoice>) -> ImPlotRect {
-    let y_axis_choice_i32 = y_axis_choice_option_to_i32(y_axis_choice);
+pub fn get_plot_limits(
+    x_axis_choice: Option<XAxisChoice>,
+    y_axis_choice: Option<YAxisChoice>,
+) -> ImPlotRect {
     unsafe {
         sys::ImPlot_GetPlotLimits(
-            IMPLOT_AUTO, // TODO(eiz): x axis
-            y_axis_choice_i32,
+            x_axis_choice_option_to_i32(x_axis_choice),
+            y_axis_choice_option_to_i32(y_axis_choice),
         )
     }
 }
 
-// TODO(eiz): DragRect
-
 /// Set the axis to be used for any upcoming plot elements
 #[rustversion::attr(since(1.48), doc(alias = "SetPlotYAxis"))]
 pub fn set_axis(axis: Axis) {
@@ -545,3 +865,105 @@ pub fn show_demo_window(show: &mut bool) {
         implot_sys::ImPlot_ShowDemoWindow(show);
     }
 }
+
+// --- Test utilities ----------------------------------------------------------------------------
+#[cfg(test)]
+pub(crate) mod test_utils {
+    use crate::{Context, PlotUi};
+    use parking_lot::Mutex;
+
+    // The imgui and implot contexts are process-global, so tests that need them cannot run
+    // in parallel. This mutex makes them take turns.
+    static TEST_MUTEX: Mutex<()> = parking_lot::const_mutex(());
+
+    /// Call `f` with a freshly created implot context, without running any frames. This is
+    /// enough for testing things that only change the context, such as the style.
+    pub(crate) fn with_context<F: FnOnce(&mut Context)>(f: F) {
+        let _guard = TEST_MUTEX.lock();
+        let _imgui = imgui::Context::create();
+        let mut plot_context = Context::create();
+        f(&mut plot_context);
+    }
+
+    /// Run `frame_count` imgui frames with a freshly created implot context, calling `f` with
+    /// the frame index during each of them. Plots created in `f` end up in imgui's implicit
+    /// debug window, which is enough for testing setup and querying functionality.
+    pub(crate) fn run_frames<F: FnMut(usize, &PlotUi)>(frame_count: usize, mut f: F) {
+        let _guard = TEST_MUTEX.lock();
+        let mut imgui = imgui::Context::create();
+        imgui.set_ini_filename(None);
+        imgui.io_mut().display_size = [1024.0, 768.0];
+        imgui.io_mut().delta_time = 1.0 / 60.0;
+        imgui.fonts().build_rgba32_texture();
+
+        // Has to be created after the imgui context, since it reads the imgui style on creation
+        let plot_context = Context::create();
+        for frame in 0..frame_count {
+            imgui.new_frame();
+            f(frame, &plot_context.get_plot_ui());
+            imgui.render();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::run_frames;
+
+    #[test]
+    fn test_style_tokens_pop_on_drop() {
+        run_frames(1, |_, plot_ui| {
+            let default_weight = plot_ui.context.style().line_weight();
+            let weight = plot_ui.with_style_var(StyleVar::LineWeight(3.0), || {
+                let _color = plot_ui.push_style_color(&PlotColorElement::Line, [1.0; 4]);
+                assert_eq!(plot_ui.style_colors.borrow().len(), 1);
+                plot_ui.context.style().line_weight()
+            });
+            assert_eq!(weight, 3.0);
+            assert_eq!(plot_ui.context.style().line_weight(), default_weight);
+            assert!(plot_ui.style_vars.borrow().is_empty());
+            assert!(plot_ui.style_colors.borrow().is_empty());
+        });
+    }
+
+    #[test]
+    fn test_typed_style_vars_are_applied() {
+        run_frames(1, |_, plot_ui| {
+            let marker = plot_ui.push_style_var(StyleVar::Marker(Marker::Diamond));
+            let padding = plot_ui.push_style_var(StyleVar::PlotPadding([3.0, 4.0]));
+            assert_eq!(plot_ui.context.style().marker(), Some(Marker::Diamond));
+            assert_eq!(plot_ui.context.style().plot_padding(), [3.0, 4.0]);
+            padding.pop();
+            marker.pop();
+        });
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "popped out of order")]
+    fn test_out_of_order_pop_is_detected() {
+        let stack = RefCell::new(vec![
+            StyleVar::LineWeight(1.0),
+            StyleVar::Marker(Marker::Cross),
+        ]);
+        pop_from_stack(&stack, 0, "variable");
+    }
+
+    #[test]
+    #[should_panic(expected = "original panic")]
+    fn test_out_of_order_pop_while_unwinding_does_not_abort() {
+        struct OutOfOrderPop(RefCell<Vec<StyleVar>>);
+        impl Drop for OutOfOrderPop {
+            fn drop(&mut self) {
+                pop_from_stack(&self.0, 0, "variable");
+            }
+        }
+
+        let _pop = OutOfOrderPop(RefCell::new(vec![
+            StyleVar::LineWeight(1.0),
+            StyleVar::Marker(Marker::Cross),
+        ]));
+        panic!("original panic");
+    }
+}
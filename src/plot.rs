@@ -2,12 +2,14 @@
 //!
 //! This module defines the `Plot` struct, which is used to create a 2D plot that will
 //! contain all other objects that can be created using this library.
-use crate::{Context, PlotLocation, PlotUi, YAxisChoice, NUMBER_OF_Y_AXES};
+use crate::axis::LinkedLimits;
+use crate::{
+    Axis, AxisConfig, AxisFlags, Context, PlotLocation, PlotUi, YAxisChoice, NUMBER_OF_AXES,
+};
 use bitflags::bitflags;
 pub use imgui::Condition;
 use implot_sys as sys;
 use std::ffi::CString;
-use std::os::raw::c_char;
 use std::{cell::RefCell, rc::Rc};
 pub use sys::{ImPlotRange, ImPlotRect, ImVec2, ImVec4};
 
@@ -31,14 +33,11 @@ bitflags! {
         const NO_BOX_SELECT = sys::ImPlotFlags__ImPlotFlags_NoBoxSelect;
         /// The mouse position, in plot coordinates, will not be displayed
         const NO_MOUSE_POSITION = sys::ImPlotFlags__ImPlotFlags_NoMouseText;
-        // TODO(eiz): legend flags
         /// A child window region will not be used to capture mouse scroll (can boost performance
         /// for single ImGui window applications)
         const NO_CHILD = sys::ImPlotFlags__ImPlotFlags_NoChild;
         /// Use an aspect ratio of 1:1 for the plot
         const AXIS_EQUAL = sys::ImPlotFlags__ImPlotFlags_Equal;
-        // TODO(eiz): SetupAxis
-        // TODO(eiz): DragRect
         /// The default mouse cursor will be replaced with a crosshair when hovered
         const CROSSHAIRS = sys::ImPlotFlags__ImPlotFlags_Crosshairs;
         /// Plot data outside the plot area will be culled from rendering
@@ -46,43 +45,30 @@ bitflags! {
     }
 }
 
-#[rustversion::attr(since(1.48), doc(alias = "ImPlotAxisFlags"))]
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotLegendFlags"))]
 bitflags! {
-    /// Axis flags. Documentation copied from implot.h for convenience. ImPlot itself also
-    /// has `Lock`, which combines `LOCK_MIN` and `LOCK_MAX`, and `NoDecorations`, which combines
-    /// `NO_GRID_LINES`, `NO_TICK_MARKS` and `NO_TICK_LABELS`.
+    /// Flags for customizing the legend of a plot. Documentation copied from implot.h for
+    /// convenience.
     #[repr(transparent)]
-    pub struct AxisFlags: u32 {
+    pub struct LegendFlags: u32 {
         /// "Default" according to original docs
-        const NONE = sys::ImPlotAxisFlags__ImPlotAxisFlags_None;
-        /// Grid lines will not be displayed
-        const NO_GRID_LINES = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoGridLines;
-        /// Tick marks will not be displayed
-        const NO_TICK_MARKS = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoTickMarks;
-        /// Text labels will not be displayed
-        const NO_TICK_LABELS = sys::ImPlotAxisFlags__ImPlotAxisFlags_NoTickLabels;
-        /// A logartithmic (base 10) axis scale will be used (mutually exclusive with AxisFlags::TIME)
-        const LOG_SCALE = sys::ImPlotAxisFlags__ImPlotAxisFlags_LogScale;
-        /// Axis will display date/time formatted labels (mutually exclusive with AxisFlags::LOG_SCALE)
-        const TIME = sys::ImPlotAxisFlags__ImPlotAxisFlags_Time;
-        /// The axis will be inverted
-        const INVERT = sys::ImPlotAxisFlags__ImPlotAxisFlags_Invert;
-        /// The axis minimum value will be locked when panning/zooming
-        const LOCK_MIN = sys::ImPlotAxisFlags__ImPlotAxisFlags_LockMin;
-        /// The axis maximum value will be locked when panning/zooming
-        const LOCK_MAX = sys::ImPlotAxisFlags__ImPlotAxisFlags_LockMax;
+        const NONE = sys::ImPlotLegendFlags__ImPlotLegendFlags_None;
+        /// Legend icons will not function as hide/show buttons
+        const NO_BUTTONS = sys::ImPlotLegendFlags__ImPlotLegendFlags_NoButtons;
+        /// Plot items will not be highlighted when their legend entry is hovered
+        const NO_HIGHLIGHT_ITEM = sys::ImPlotLegendFlags__ImPlotLegendFlags_NoHighlightItem;
+        /// Axes will not be highlighted when legend entries are hovered (only relevant if x/y-axis
+        /// count > 1)
+        const NO_HIGHLIGHT_AXIS = sys::ImPlotLegendFlags__ImPlotLegendFlags_NoHighlightAxis;
+        /// The user will not be able to open context menus with right-click
+        const NO_MENUS = sys::ImPlotLegendFlags__ImPlotLegendFlags_NoMenus;
+        /// Legend will be rendered outside of the plot area
+        const OUTSIDE = sys::ImPlotLegendFlags__ImPlotLegendFlags_Outside;
+        /// Legend entries will be displayed horizontally
+        const HORIZONTAL = sys::ImPlotLegendFlags__ImPlotLegendFlags_Horizontal;
     }
 }
 
-/// Internally-used struct for storing axis limits
-#[derive(Clone)]
-enum AxisLimitSpecification {
-    /// Direct limits, specified as values
-    Single(ImPlotRange, Condition),
-    /// Limits that are linked to limits of other plots (via clones of the same Rc)
-    Linked(Rc<RefCell<ImPlotRange>>),
-}
-
 /// Struct to represent an ImPlot. This is the main construct used to contain all kinds of plots in ImPlot.
 ///
 /// `Plot` is to be used (within an imgui window) with the following pattern:
@@ -105,51 +91,18 @@ pub struct Plot {
     title: CString,
     /// Size of the plot in [x, y] direction, in the same units imgui uses.
     size: [f32; 2],
-    /// Label of the x axis, shown on the bottom. Stored as CString because that's what we'll use
-    /// afterwards, and this ensures the CString itself will stay alive long enough for the plot.
-    x_label: CString,
-    /// Label of the y axis, shown on the left. Stored as CString because that's what we'll use
-    /// afterwards, and this ensures the CString itself will stay alive long enough for the plot.
-    y_label: CString,
-    /// X axis limits, if present
-    x_limits: Option<AxisLimitSpecification>,
-    /// Y axis limits, if present
-    y_limits: [Option<AxisLimitSpecification>; NUMBER_OF_Y_AXES],
-    /// Positions for custom X axis ticks, if any
-    x_tick_positions: Option<Vec<f64>>,
-    /// Labels for custom X axis ticks, if any. I'd prefer to store these together
-    /// with the positions in one vector of an algebraic data type, but this would mean extra
-    /// copies when it comes time to draw the plot because the C++ library expects separate lists.
-    /// The data is stored as CStrings because those are null-terminated, and since we have to
-    /// convert to null-terminated data anyway, we may as well do that directly instead of cloning
-    /// Strings and converting them afterwards.
-    x_tick_labels: Option<Vec<CString>>,
-    /// Whether to also show the default X ticks when showing custom ticks or not
-    show_x_default_ticks: bool,
-    /// Positions for custom Y axis ticks, if any
-    y_tick_positions: [Option<Vec<f64>>; NUMBER_OF_Y_AXES],
-    /// Labels for custom Y axis ticks, if any. I'd prefer to store these together
-    /// with the positions in one vector of an algebraic data type, but this would mean extra
-    /// copies when it comes time to draw the plot because the C++ library expects separate lists.
-    /// The data is stored as CStrings because those are null-terminated, and since we have to
-    /// convert to null-terminated data anyway, we may as well do that directly instead of cloning
-    /// Strings and converting them afterwards.
-    y_tick_labels: [Option<Vec<CString>>; NUMBER_OF_Y_AXES],
-    /// Whether to also show the default Y ticks when showing custom ticks or not
-    show_y_default_ticks: [bool; NUMBER_OF_Y_AXES],
-    // Configuration for the legend, if specified. The tuple contains location, orientation
-    // and a boolean (true means legend is outside of plot, false means within). If nothing
-    // is set, implot's defaults are used. Note also  that if these are set, then implot's
-    // interactive legend configuration does not work because it is overridden by the settings
-    // here.
-    // TODO(eiz): legend configuration
-    //legend_configuration: Option<(PlotLocation, PlotOrientation, bool)>,
+    /// Configuration of the axes (label, flags, limits, ticks and so on), if present. Indexed by
+    /// `Axis`, so this covers X1-X3 and Y1-Y3. X1 and Y1 are always shown, the other axes are
+    /// only enabled if they have a configuration.
+    axis_configs: [Option<AxisConfig>; NUMBER_OF_AXES],
+    /// Configuration for the legend, if specified. The tuple contains location and flags, the
+    /// latter of which determine orientation and whether the legend is drawn outside of the plot.
+    /// If nothing is set, implot's defaults are used. Note also that if these are set, then
+    /// implot's interactive legend configuration does not work because it is overridden by the
+    /// settings here.
+    legend_configuration: Option<(PlotLocation, LegendFlags)>,
     /// Flags relating to the plot TODO(4bb4) make those into bitflags
     plot_flags: sys::ImPlotFlags,
-    /// Flags relating to the X axis of the plot TODO(4bb4) make those into bitflags
-    x_flags: sys::ImPlotAxisFlags,
-    /// Flags relating to the each of the Y axes of the plot TODO(4bb4) make those into bitflags
-    y_flags: [sys::ImPlotAxisFlags; NUMBER_OF_Y_AXES],
 }
 
 impl Plot {
@@ -160,29 +113,14 @@ impl Plot {
     /// # Panics
     /// Will panic if the title string contains internal null bytes.
     pub fn new(title: &str) -> Self {
-        // Needed for initialization, see https://github.com/rust-lang/rust/issues/49147
-        const POS_NONE: Option<Vec<f64>> = None;
-        const TICK_NONE: Option<Vec<CString>> = None;
-
         // TODO(4bb4) question these defaults, maybe remove some of them
         Self {
             title: CString::new(title)
                 .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", title)),
             size: [DEFAULT_PLOT_SIZE_X, DEFAULT_PLOT_SIZE_Y],
-            x_label: CString::new("").unwrap(),
-            y_label: CString::new("").unwrap(),
-            x_limits: None,
-            y_limits: Default::default(),
-            x_tick_positions: None,
-            x_tick_labels: None,
-            show_x_default_ticks: false,
-            y_tick_positions: [POS_NONE; NUMBER_OF_Y_AXES],
-            y_tick_labels: [TICK_NONE; NUMBER_OF_Y_AXES],
-            show_y_default_ticks: [false; NUMBER_OF_Y_AXES],
-            // TODO(eiz) legend_configuration: None,
+            axis_configs: Default::default(),
+            legend_configuration: None,
             plot_flags: PlotFlags::ANTIALIASED.bits() as sys::ImPlotFlags,
-            x_flags: AxisFlags::NONE.bits() as sys::ImPlotAxisFlags,
-            y_flags: [AxisFlags::NONE.bits() as sys::ImPlotAxisFlags; NUMBER_OF_Y_AXES],
         }
     }
 
@@ -194,15 +132,32 @@ impl Plot {
         self
     }
 
+    /// Set the full configuration of the given axis, see [`AxisConfig`] for what can be
+    /// configured. This replaces anything set for the axis before, for example through
+    /// [`Plot::x_label`] or [`Plot::axis_limits`]. Configuring one of the additional axes
+    /// (X2, X3, Y2, Y3) enables it.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxis"))]
+    #[inline]
+    pub fn with_axis(mut self, axis: Axis, config: AxisConfig) -> Self {
+        self.axis_configs[axis as usize] = Some(config);
+        self
+    }
+
+    /// Internal helper function to modify the configuration of the given axis, starting from
+    /// the default configuration if the axis has not been configured yet.
+    fn map_axis_config<F: FnOnce(AxisConfig) -> AxisConfig>(mut self, axis: Axis, f: F) -> Self {
+        let config = self.axis_configs[axis as usize].take().unwrap_or_default();
+        self.axis_configs[axis as usize] = Some(f(config));
+        self
+    }
+
     /// Set the x label of the plot
     ///
     /// # Panics
     /// Will panic if the label string contains internal null bytes.
     #[inline]
-    pub fn x_label(mut self, label: &str) -> Self {
-        self.x_label = CString::new(label)
-            .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
-        self
+    pub fn x_label(self, label: &str) -> Self {
+        self.map_axis_config(Axis::X1, |config| config.label(label))
     }
 
     /// Set the y label of the plot
@@ -210,31 +165,64 @@ impl Plot {
     /// # Panics
     /// Will panic if the label string contains internal null bytes.
     #[inline]
-    pub fn y_label(mut self, label: &str) -> Self {
-        self.y_label = CString::new(label)
-            .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", label));
-        self
+    pub fn y_label(self, label: &str) -> Self {
+        self.map_axis_config(Axis::Y1, |config| config.label(label))
     }
 
-    /// Set the x limits of the plot.
+    /// Set the limits of the plot for the given axis. This works for all axes, including the
+    /// additional X and Y axes - setting limits on those enables them. The `condition` determines
+    /// when the limits are applied, `Condition::Always` forces them on every frame while
+    /// `Condition::Once` only sets them when the plot is first shown.
+    ///
+    /// Note: This conflicts with the linked limits setters, whichever is called last on plot
+    /// construction takes effect for a given axis.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisLimits"))]
+    #[inline]
+    pub fn axis_limits<L: Into<ImPlotRange>>(
+        self,
+        axis: Axis,
+        limits: L,
+        condition: Condition,
+    ) -> Self {
+        self.map_axis_config(axis, |config| config.limits(limits, condition))
+    }
+
+    /// Set the x limits of the plot. To set limits for the other X axes, use
+    /// [`Plot::axis_limits`].
     ///
     /// Note: This conflicts with `linked_x_limits`, whichever is called last on plot construction
     /// takes effect.
     #[inline]
-    pub fn x_limits<L: Into<ImPlotRange>>(mut self, limits: L, condition: Condition) -> Self {
-        self.x_limits = Some(AxisLimitSpecification::Single(limits.into(), condition));
-        self
+    pub fn x_limits<L: Into<ImPlotRange>>(self, limits: L, condition: Condition) -> Self {
+        self.axis_limits(Axis::X1, limits, condition)
+    }
+
+    /// Set linked limits for the given axis. Pass clones of the same `Rc` into other plots (or
+    /// other axes of other plots) to link their limits with the same values - zooming or panning
+    /// one of them then moves all of them. Linking a secondary axis enables it.
+    ///
+    /// The range is read when the plot is begun and written back when it is ended, so it must
+    /// not be borrowed at either of these points, which panics. Within the closure passed to
+    /// [`Plot::build`], it can be used freely. The same range can be linked to several axes of
+    /// one plot, e.g. to zoom the X and Y axes together.
+    ///
+    /// Note: This conflicts with the direct limit setters, whichever is called last on plot
+    /// construction takes effect for a given axis.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisLinks"))]
+    #[inline]
+    pub fn linked_axis_limits(self, axis: Axis, limits: Rc<RefCell<ImPlotRange>>) -> Self {
+        self.map_axis_config(axis, |config| config.linked_limits(limits))
     }
 
     /// Set linked x limits for this plot. Pass clones of the same `Rc` into other plots
-    /// to link their limits with the same values.
+    /// to link their limits with the same values. See [`Plot::linked_axis_limits`] for
+    /// details and for linking the other X axes.
     ///
     /// Note: This conflicts with `x_limits`, whichever is called last on plot construction takes
     /// effect.
     #[inline]
-    pub fn linked_x_limits(mut self, limits: Rc<RefCell<ImPlotRange>>) -> Self {
-        self.x_limits = Some(AxisLimitSpecification::Linked(limits));
-        self
+    pub fn linked_x_limits(self, limits: Rc<RefCell<ImPlotRange>>) -> Self {
+        self.linked_axis_limits(Axis::X1, limits)
     }
 
     /// Set the Y limits of the plot for the given Y axis. Call multiple times with different
@@ -245,14 +233,12 @@ impl Plot {
     /// takes effect for a given axis.
     #[inline]
     pub fn y_limits<L: Into<ImPlotRange>>(
-        mut self,
+        self,
         limits: L,
         y_axis_choice: YAxisChoice,
         condition: Condition,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
-        self.y_limits[axis_index] = Some(AxisLimitSpecification::Single(limits.into(), condition));
-        self
+        self.axis_limits(y_axis_choice.into(), limits, condition)
     }
 
     /// Convenience function to directly set the Y limits for the first Y axis. To programmatically
@@ -279,19 +265,17 @@ impl Plot {
     /// Set linked Y limits of the plot for the given Y axis. Pass clones of the same `Rc` into
     /// other plots to link their limits with the same values. Call multiple times with different
     /// `y_axis_choice` values to set for multiple axes, or use the convenience methods such as
-    /// [`Plot::y1_limits`].
+    /// [`Plot::y1_limits`]. See [`Plot::linked_axis_limits`] for details.
     ///
     /// Note: This conflicts with `y_limits`, whichever is called last on plot construction takes
     /// effect for a given axis.
     #[inline]
     pub fn linked_y_limits(
-        mut self,
+        self,
         limits: Rc<RefCell<ImPlotRange>>,
         y_axis_choice: YAxisChoice,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
-        self.y_limits[axis_index] = Some(AxisLimitSpecification::Linked(limits));
-        self
+        self.linked_axis_limits(y_axis_choice.into(), limits)
     }
 
     /// Convenience function to directly set linked Y limits for the first Y axis. To
@@ -318,30 +302,91 @@ impl Plot {
         self.linked_y_limits(limits, YAxisChoice::Third)
     }
 
-    /// Set X ticks without labels for the plot. The vector contains one label each in
-    /// the form of a tuple `(label_position, label_string)`. The `show_default` setting
-    /// determines whether the default ticks are also shown.
+    /// Set ticks without labels for the given axis, at the given positions. The `show_default`
+    /// setting determines whether the default ticks are also shown. Setting ticks on a secondary
+    /// axis enables it.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
     #[inline]
-    pub fn x_ticks(mut self, ticks: &[f64], show_default: bool) -> Self {
-        self.x_tick_positions = Some(ticks.into());
-        self.show_x_default_ticks = show_default;
-        self
+    pub fn axis_ticks(self, axis: Axis, ticks: &[f64], show_default: bool) -> Self {
+        self.map_axis_config(axis, |config| config.ticks(ticks, show_default))
     }
 
-    /// Set X ticks without labels for the plot. The vector contains one label each in
-    /// the form of a tuple `(label_position, label_string)`. The `show_default` setting
-    /// determines whether the default ticks are also shown.
+    /// Set ticks with labels for the given axis. The slice contains one position and label
+    /// each in the form of a tuple `(label_position, label_string)`. The `show_default`
+    /// setting determines whether the default ticks are also shown. Setting ticks on a secondary
+    /// axis enables it.
+    ///
+    /// # Panics
+    /// Will panic if any of the tick label strings contain internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
     #[inline]
-    pub fn y_ticks(
-        mut self,
-        y_axis_choice: YAxisChoice,
-        ticks: &[f64],
+    pub fn axis_ticks_with_labels(
+        self,
+        axis: Axis,
+        tick_labels: &[(f64, String)],
         show_default: bool,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
-        self.y_tick_positions[axis_index] = Some(ticks.into());
-        self.show_y_default_ticks[axis_index] = show_default;
-        self
+        self.map_axis_config(axis, |config| {
+            config.ticks_with_labels(tick_labels, show_default)
+        })
+    }
+
+    /// Set `count` ticks without labels for the given axis, evenly spaced between `min` and
+    /// `max` (both inclusive). The `show_default` setting determines whether the default ticks
+    /// are also shown. Setting ticks on a secondary axis enables it.
+    ///
+    /// # Panics
+    /// Will panic if `count` is smaller than two, since that can't span a range.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn axis_ticks_range(
+        self,
+        axis: Axis,
+        min: f64,
+        max: f64,
+        count: u32,
+        show_default: bool,
+    ) -> Self {
+        self.map_axis_config(axis, |config| {
+            config.ticks_range(min, max, count, show_default)
+        })
+    }
+
+    /// Set ticks with labels for the given axis, evenly spaced between `min` and `max` (both
+    /// inclusive). One tick is created per label. The `show_default` setting determines whether
+    /// the default ticks are also shown. Setting ticks on a secondary axis enables it.
+    ///
+    /// # Panics
+    /// Will panic if there are fewer than two labels, or if any of the tick label strings contain
+    /// internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupAxisTicks"))]
+    #[inline]
+    pub fn axis_ticks_range_with_labels(
+        self,
+        axis: Axis,
+        min: f64,
+        max: f64,
+        labels: &[&str],
+        show_default: bool,
+    ) -> Self {
+        self.map_axis_config(axis, |config| {
+            config.ticks_range_with_labels(min, max, labels, show_default)
+        })
+    }
+
+    /// Set X ticks without labels for the plot, at the given positions. The `show_default`
+    /// setting determines whether the default ticks are also shown. For the other X axes, use
+    /// [`Plot::axis_ticks`].
+    #[inline]
+    pub fn x_ticks(self, ticks: &[f64], show_default: bool) -> Self {
+        self.axis_ticks(Axis::X1, ticks, show_default)
+    }
+
+    /// Set Y ticks without labels for the given Y axis, at the given positions. The
+    /// `show_default` setting determines whether the default ticks are also shown.
+    #[inline]
+    pub fn y_ticks(self, y_axis_choice: YAxisChoice, ticks: &[f64], show_default: bool) -> Self {
+        self.axis_ticks(y_axis_choice.into(), ticks, show_default)
     }
 
     /// Set X ticks with labels for the plot. The vector contains one position and label
@@ -351,23 +396,8 @@ impl Plot {
     /// # Panics
     /// Will panic if any of the tick label strings contain internal null bytes.
     #[inline]
-    pub fn x_ticks_with_labels(
-        mut self,
-        tick_labels: &[(f64, String)],
-        show_default: bool,
-    ) -> Self {
-        self.x_tick_positions = Some(tick_labels.iter().map(|x| x.0).collect());
-        self.x_tick_labels = Some(
-            tick_labels
-                .iter()
-                .map(|x| {
-                    CString::new(x.1.as_str())
-                        .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", x.1))
-                })
-                .collect(),
-        );
-        self.show_x_default_ticks = show_default;
-        self
+    pub fn x_ticks_with_labels(self, tick_labels: &[(f64, String)], show_default: bool) -> Self {
+        self.axis_ticks_with_labels(Axis::X1, tick_labels, show_default)
     }
 
     /// Set Y ticks with labels for the plot. The vector contains one position and label
@@ -378,24 +408,12 @@ impl Plot {
     /// Will panic if any of the tick label strings contain internal null bytes.
     #[inline]
     pub fn y_ticks_with_labels(
-        mut self,
+        self,
         y_axis_choice: YAxisChoice,
         tick_labels: &[(f64, String)],
         show_default: bool,
     ) -> Self {
-        let axis_index = y_axis_choice as usize;
-        self.y_tick_positions[axis_index] = Some(tick_labels.iter().map(|x| x.0).collect());
-        self.y_tick_labels[axis_index] = Some(
-            tick_labels
-                .iter()
-                .map(|x| {
-                    CString::new(x.1.as_str())
-                        .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", x.1))
-                })
-                .collect(),
-        );
-        self.show_y_default_ticks[axis_index] = show_default;
-        self
+        self.axis_ticks_with_labels(y_axis_choice.into(), tick_labels, show_default)
     }
 
     /// Set the plot flags, see the help for `PlotFlags` for what the available flags are
@@ -407,174 +425,26 @@ impl Plot {
 
     /// Set the axis flags for the X axis in this plot
     #[inline]
-    pub fn with_x_axis_flags(mut self, flags: &AxisFlags) -> Self {
-        self.x_flags = flags.bits() as sys::ImPlotAxisFlags;
-        self
+    pub fn with_x_axis_flags(self, flags: &AxisFlags) -> Self {
+        self.map_axis_config(Axis::X1, |config| config.with_flags(flags))
     }
 
-    /// Set the axis flags for the selected Y axis in this plot
+    /// Set the axis flags for the selected Y axis in this plot. Setting flags for one of the
+    /// additional Y axes enables it. The flags replace the default flags of the axis, see
+    /// [`AxisConfig::with_flags`].
     #[inline]
-    pub fn with_y_axis_flags(mut self, y_axis_choice: YAxisChoice, flags: &AxisFlags) -> Self {
-        let axis_index = y_axis_choice as usize;
-        self.y_flags[axis_index] = flags.bits() as sys::ImPlotAxisFlags;
-        self
+    pub fn with_y_axis_flags(self, y_axis_choice: YAxisChoice, flags: &AxisFlags) -> Self {
+        self.map_axis_config(y_axis_choice.into(), |config| config.with_flags(flags))
     }
 
-    /* TODO(eiz): legend
-    /// Set the legend location, orientation and whether it is to be drawn outside the plot
-    #[rustversion::attr(since(1.48), doc(alias = "SetLegendLocation"))]
+    /// Set the legend location and flags. The flags determine, among other things, whether
+    /// the legend entries are laid out horizontally and whether the legend is drawn outside of
+    /// the plot area.
+    #[rustversion::attr(since(1.48), doc(alias = "SetupLegend"))]
     #[inline]
-    pub fn with_legend_location(
-        mut self,
-        location: &PlotLocation,
-        orientation: &PlotOrientation,
-        outside: bool,
-    ) -> Self {
-        self.legend_configuration = Some((*location, *orientation, outside));
+    pub fn with_legend(mut self, location: PlotLocation, flags: LegendFlags) -> Self {
+        self.legend_configuration = Some((location, flags));
         self
-    }*/
-
-    /// Internal helper function to set axis limits in case they are specified.
-    fn maybe_set_axis_limits(&self) {
-        // Limit-setting can either happen via direct limits or through linked limits. The version
-        // of implot we link to here has different APIs for the two (separate per-axis calls for
-        // direct, and one call for everything together for linked), hence the code here is a bit
-        // clunky and takes the two approaches separately instead of a unified "match".
-
-        // --- Direct limit-setting ---
-        if let Some(AxisLimitSpecification::Single(limits, condition)) = &self.x_limits {
-            unsafe {
-                /* TODO(eiz): SetNextAxisLimits
-                sys::ImPlot_SetNextPlotLimitsX(
-                    limits.Min,
-                    limits.Max,
-                    *condition as sys::ImGuiCond,
-                );*/
-            }
-        }
-
-        self.y_limits
-            .iter()
-            .enumerate()
-            .for_each(|(k, limit_spec)| {
-                if let Some(AxisLimitSpecification::Single(limits, condition)) = limit_spec {
-                    unsafe {
-                        /* TODO(eiz): SetNextAxisLimits
-                        sys::ImPlot_SetNextPlotLimitsY(
-                            limits.Min,
-                            limits.Max,
-                            *condition as sys::ImGuiCond,
-                            k as i32,
-                        );*/
-                    }
-                }
-            });
-
-        // --- Linked limit-setting ---
-        let (xmin_pointer, xmax_pointer) =
-            if let Some(AxisLimitSpecification::Linked(value)) = &self.x_limits {
-                let mut borrowed = value.borrow_mut();
-                (
-                    &mut (*borrowed).Min as *mut _,
-                    &mut (*borrowed).Max as *mut _,
-                )
-            } else {
-                (std::ptr::null_mut(), std::ptr::null_mut())
-            };
-
-        let y_limit_pointers: Vec<(*mut f64, *mut f64)> = self
-            .y_limits
-            .iter()
-            .map(|limit_spec| {
-                if let Some(AxisLimitSpecification::Linked(value)) = limit_spec {
-                    let mut borrowed = value.borrow_mut();
-                    (
-                        &mut (*borrowed).Min as *mut _,
-                        &mut (*borrowed).Max as *mut _,
-                    )
-                } else {
-                    (std::ptr::null_mut(), std::ptr::null_mut())
-                }
-            })
-            .collect();
-
-        unsafe {
-            // Calling this unconditionally here as calling it with all NULL pointers should not
-            // affect anything. In terms of unsafety, the pointers should be OK as long as any plot
-            // struct that has an Rc to the same data is alive.
-            /* TODO(eiz): SetNextAxisLinks
-            sys::ImPlot_LinkNextPlotLimits(
-                xmin_pointer,
-                xmax_pointer,
-                y_limit_pointers[0].0,
-                y_limit_pointers[0].1,
-                y_limit_pointers[1].0,
-                y_limit_pointers[1].1,
-                y_limit_pointers[2].0,
-                y_limit_pointers[2].1,
-            )*/
-        }
-    }
-
-    /// Internal helper function to set tick labels in case they are specified. This does the
-    /// preparation work that is the same for both the X and Y axis plots, then calls the
-    /// "set next plot ticks" wrapper functions for both X and Y.
-    fn maybe_set_tick_labels(&self) {
-        // Show x ticks if they are available
-        if self.x_tick_positions.is_some() && !self.x_tick_positions.as_ref().unwrap().is_empty() {
-            let mut pointer_vec; // The vector of pointers we create has to have a longer lifetime
-            let labels_pointer = if let Some(labels_value) = &self.x_tick_labels {
-                pointer_vec = labels_value
-                    .iter()
-                    .map(|x| x.as_ptr() as *const c_char)
-                    .collect::<Vec<*const c_char>>();
-                pointer_vec.as_mut_ptr()
-            } else {
-                std::ptr::null_mut()
-            };
-
-            unsafe {
-                /* TODO(eiz): SetupAxisTicks
-                sys::ImPlot_SetNextPlotTicksXdoublePtr(
-                    self.x_tick_positions.as_ref().unwrap().as_ptr(),
-                    self.x_tick_positions.as_ref().unwrap().len() as i32,
-                    labels_pointer,
-                    self.show_x_default_ticks,
-                )*/
-            }
-        }
-
-        self.y_tick_positions
-            .iter()
-            .zip(self.y_tick_labels.iter())
-            .zip(self.show_y_default_ticks.iter())
-            .enumerate()
-            .for_each(|(k, ((positions, labels), show_defaults))| {
-                if positions.is_some() && !positions.as_ref().unwrap().is_empty() {
-                    // The vector of pointers we create has to have a longer lifetime
-                    let mut pointer_vec;
-                    let labels_pointer = if let Some(labels_value) = &labels {
-                        pointer_vec = labels_value
-                            .iter()
-                            .map(|x| x.as_ptr() as *const c_char)
-                            .collect::<Vec<*const c_char>>();
-                        pointer_vec.as_mut_ptr()
-                    } else {
-                        std::ptr::null_mut()
-                    };
-
-                    unsafe {
-                        /* TODO(eiz): SetupAxisTicks
-                        sys::ImPlot_SetNextPlotTicksYdoublePtr(
-                            positions.as_ref().unwrap().as_ptr(),
-                            positions.as_ref().unwrap().len() as i32,
-                            labels_pointer,
-                            *show_defaults,
-                            k as i32,
-                        )*/
-                    }
-                }
-            });
     }
 
     /// Attempt to show the plot. If this returns a token, the plot will actually
@@ -586,39 +456,54 @@ impl Plot {
     /// instead.
     #[rustversion::attr(since(1.48), doc(alias = "BeginPlot"))]
     pub fn begin(&self, plot_ui: &PlotUi) -> Option<PlotToken> {
-        self.maybe_set_axis_limits();
-        self.maybe_set_tick_labels();
+        // Copy the linked limits before anything is begun, so that a range which can't be
+        // borrowed panics while ImPlot's plot stack is still untouched.
+        let mut linked_limits = LinkedLimits::default();
+        self.axis_configs
+            .iter()
+            .filter_map(|config| config.as_ref().and_then(AxisConfig::linked_range))
+            .for_each(|limits| {
+                linked_limits.copy_of(limits);
+            });
 
         let should_render = unsafe {
             let size_vec: ImVec2 = ImVec2 {
                 x: self.size[0],
                 y: self.size[1],
             };
-            // TODO(eiz): SetupAxis
             sys::ImPlot_BeginPlot(self.title.as_ptr(), size_vec, self.plot_flags)
         };
 
         if should_render {
-            // Configure legend location, if one was set. This has to be called between begin() and
-            // end(), but since only the last call to it actually affects the outcome, I'm adding
-            // it here instead of as a freestanding function. If this is too restrictive (for
-            // example, if you want to set the location based on code running _during_ the plotting
-            // for some reason), file an issue and we'll move it.
-            /* TODO(eiz): legend configuration
-            if let Some(legend_config) = &self.legend_configuration {
-                // We introduce variables with typechecks here to safeguard against accidental
-                // changes in order in the config tuple
-                let location: PlotLocation = legend_config.0;
-                let orientation: PlotOrientation = legend_config.1;
-                let outside_plot: bool = legend_config.2;
+            // Set up the configured axes. This has to happen between BeginPlot and SetupFinish,
+            // since that is where ImPlot accepts setup calls. The copies of linked limits have
+            // to be kept alive until the plot is ended, see AxisConfig::setup.
+            for axis in &[Axis::X1, Axis::X2, Axis::X3, Axis::Y1, Axis::Y2, Axis::Y3] {
+                if let Some(config) = &self.axis_configs[*axis as usize] {
+                    config.setup(*axis, &mut linked_limits);
+                }
+            }
+
+            // Configure the legend, if a configuration was set. Like the axes, this has to be
+            // done between BeginPlot and SetupFinish, which is why it is part of the plot
+            // configuration instead of a freestanding function.
+            if let Some((location, flags)) = self.legend_configuration {
                 unsafe {
-                    sys::ImPlot_SetLegendLocation(location as i32, orientation as i32, outside_plot)
+                    sys::ImPlot_SetupLegend(
+                        location as sys::ImPlotLocation,
+                        flags.bits() as sys::ImPlotLegendFlags,
+                    );
                 }
-            }*/
+            }
+
+            // Lock the setup explicitly, so the setup calls above are guaranteed to be applied
+            // before anything is drawn, even if nothing is plotted.
+            unsafe { sys::ImPlot_SetupFinish() };
 
             Some(PlotToken {
                 context: plot_ui.context,
                 plot_title: self.title.clone(),
+                linked_limits,
             })
         } else {
             // In contrast with imgui windows, end() does not have to be
@@ -647,6 +532,9 @@ pub struct PlotToken {
     context: *const Context,
     /// For better error messages
     plot_title: CString,
+    /// Copies of the linked axis limits of the plot. ImPlot keeps pointers to these until the
+    /// plot is ended, after which they are written back to the linked ranges.
+    linked_limits: LinkedLimits,
 }
 
 impl PlotToken {
@@ -655,6 +543,7 @@ impl PlotToken {
     pub fn end(mut self) {
         self.context = std::ptr::null();
         unsafe { sys::ImPlot_EndPlot() };
+        self.linked_limits.write_back();
     }
 }
 
@@ -668,3 +557,145 @@ impl Drop for PlotToken {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_plot_limits, test_utils::run_frames, AxisScale, XAxisChoice};
+
+    /// Assert that an axis range is what we expect it to be
+    fn assert_range_eq(range: &ImPlotRange, expected: [f64; 2]) {
+        assert_eq!([range.Min, range.Max], expected);
+    }
+
+    #[test]
+    fn test_axis_limits_are_applied() {
+        run_frames(1, |_, plot_ui| {
+            let mut limits = None;
+            Plot::new("Limits")
+                .x_limits([1.0, 2.0], Condition::Always)
+                .axis_limits(Axis::X2, [3.0, 4.0], Condition::Always)
+                .axis_limits(Axis::X3, [11.0, 12.0], Condition::Always)
+                .y1_limits([5.0, 6.0], Condition::Always)
+                .y2_limits([7.0, 8.0], Condition::Always)
+                .y3_limits([9.0, 10.0], Condition::Always)
+                .build(plot_ui, || {
+                    limits = Some((
+                        get_plot_limits(Some(XAxisChoice::First), Some(YAxisChoice::First)),
+                        get_plot_limits(Some(XAxisChoice::Second), Some(YAxisChoice::Second)),
+                        get_plot_limits(Some(XAxisChoice::Third), Some(YAxisChoice::Third)),
+                    ));
+                });
+
+            let (first, second, third) = limits.expect("Plot was not drawn");
+            assert_range_eq(&first.X, [1.0, 2.0]);
+            assert_range_eq(&first.Y, [5.0, 6.0]);
+            assert_range_eq(&second.X, [3.0, 4.0]);
+            assert_range_eq(&second.Y, [7.0, 8.0]);
+            assert_range_eq(&third.X, [11.0, 12.0]);
+            assert_range_eq(&third.Y, [9.0, 10.0]);
+        });
+    }
+
+    #[test]
+    fn test_axis_config_is_applied() {
+        run_frames(1, |_, plot_ui| {
+            let mut limits = None;
+            Plot::new("Axis config")
+                .with_axis(
+                    Axis::Y3,
+                    AxisConfig::new()
+                        .label("Third")
+                        .with_flags(&AxisFlags::OPPOSITE)
+                        .limits([10.0, 1000.0], Condition::Always)
+                        .ticks(&[10.0, 100.0, 1000.0], false)
+                        .with_scale(AxisScale::Log10),
+                )
+                .y_label("First")
+                .y1_limits([0.0, 1.0], Condition::Always)
+                .build(plot_ui, || {
+                    limits = Some((
+                        get_plot_limits(None, Some(YAxisChoice::First)),
+                        get_plot_limits(None, Some(YAxisChoice::Third)),
+                    ));
+                });
+
+            // Setting the label after the limits must not have reset the limits of Y1
+            let (y1, y3) = limits.expect("Plot was not drawn");
+            assert_range_eq(&y1.Y, [0.0, 1.0]);
+            assert_range_eq(&y3.Y, [10.0, 1000.0]);
+        });
+    }
+
+    #[test]
+    fn test_axis_limits_honour_condition() {
+        run_frames(2, |frame, plot_ui| {
+            // The "once" limits change between frames, but should only be applied on the first
+            // one, while the "always" limits should follow along.
+            let offset = frame as f64 * 10.0;
+            let mut limits = None;
+            Plot::new("Limit conditions")
+                .x_limits([offset, offset + 1.0], Condition::Once)
+                .y1_limits([offset, offset + 1.0], Condition::Always)
+                .build(plot_ui, || {
+                    limits = Some(get_plot_limits(None, None));
+                });
+
+            let limits = limits.expect("Plot was not drawn");
+            assert_range_eq(&limits.X, [0.0, 1.0]);
+            assert_range_eq(&limits.Y, [offset, offset + 1.0]);
+        });
+    }
+
+    #[test]
+    fn test_linked_axis_limits() {
+        let linked_x = Rc::new(RefCell::new(ImPlotRange { Min: 0.0, Max: 1.0 }));
+        let linked_y2 = Rc::new(RefCell::new(ImPlotRange { Min: 2.0, Max: 3.0 }));
+        run_frames(2, |frame, plot_ui| {
+            // Changes to the shared range between frames have to show up in all linked plots
+            if frame == 1 {
+                *linked_x.borrow_mut() = ImPlotRange { Min: 5.0, Max: 6.0 };
+            }
+            let expected_x = if frame == 0 { [0.0, 1.0] } else { [5.0, 6.0] };
+
+            for title in &["Linked 1", "Linked 2"] {
+                let mut limits = None;
+                Plot::new(title)
+                    .linked_x_limits(linked_x.clone())
+                    .linked_y2_limits(linked_y2.clone())
+                    .build(plot_ui, || {
+                        limits = Some(get_plot_limits(None, Some(YAxisChoice::Second)));
+                    });
+
+                let limits = limits.expect("Plot was not drawn");
+                assert_range_eq(&limits.X, expected_x);
+                assert_range_eq(&limits.Y, [2.0, 3.0]);
+            }
+
+            // The borrows taken while building the plots have to be released again
+            assert!(linked_x.try_borrow_mut().is_ok());
+            assert!(linked_y2.try_borrow_mut().is_ok());
+        });
+    }
+
+    #[test]
+    fn test_same_range_linked_to_two_axes() {
+        let linked = Rc::new(RefCell::new(ImPlotRange { Min: 1.0, Max: 2.0 }));
+        run_frames(2, |_, plot_ui| {
+            let mut limits = None;
+            Plot::new("Linked X and Y")
+                .linked_x_limits(linked.clone())
+                .linked_y1_limits(linked.clone())
+                .build(plot_ui, || {
+                    // The range is not borrowed while the plot is drawn
+                    assert_range_eq(&linked.borrow(), [1.0, 2.0]);
+                    limits = Some(get_plot_limits(None, None));
+                });
+
+            let limits = limits.expect("Plot was not drawn");
+            assert_range_eq(&limits.X, [1.0, 2.0]);
+            assert_range_eq(&limits.Y, [1.0, 2.0]);
+            assert_range_eq(&linked.borrow(), [1.0, 2.0]);
+        });
+    }
+}
@@ -0,0 +1,496 @@
+//! # Plot data module
+//!
+//! This module defines the `PlotData` trait, which is implemented for the numeric types ImPlot
+//! can plot directly. Plot elements that are generic over it dispatch to the matching typed
+//! variant of the C++ plotting function, so the data is passed on without conversion.
+use crate::sys::{self, ImPlotPoint};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+
+mod private {
+    /// Supertrait of `PlotData`, which keeps it from being implemented outside of this crate.
+    /// The C++ code only has plotting functions for a fixed set of types, so other
+    /// implementations could not do anything useful anyway.
+    pub trait Sealed {}
+}
+
+/// Signature of the line, scatter and stairs plotting functions taking x and y values
+#[doc(hidden)]
+pub type XYFn<T> = unsafe extern "C" fn(*const c_char, *const T, *const T, i32, i32, i32);
+
+/// Signature of the vertical and horizontal bar plotting functions taking x and y values
+#[doc(hidden)]
+pub type BarsFn<T> = unsafe extern "C" fn(*const c_char, *const T, *const T, i32, f64, i32, i32);
+
+/// Signature of the `PlotStems` variant taking explicit x values
+#[doc(hidden)]
+pub type StemsFn<T> = unsafe extern "C" fn(*const c_char, *const T, *const T, i32, f64, i32, i32);
+
+/// Signature of the `PlotStems` variant taking values only, with implicit x values
+#[doc(hidden)]
+pub type StemsValuesFn<T> =
+    unsafe extern "C" fn(*const c_char, *const T, i32, f64, f64, f64, i32, i32);
+
+/// Signature of the `PlotShaded` variant shading between a line and a reference y value
+#[doc(hidden)]
+pub type ShadedFn<T> = unsafe extern "C" fn(*const c_char, *const T, *const T, i32, f64, i32, i32);
+
+/// Signature of the `PlotShaded` variant shading between two lines
+#[doc(hidden)]
+pub type ShadedBetweenFn<T> =
+    unsafe extern "C" fn(*const c_char, *const T, *const T, *const T, i32, i32, i32);
+
+/// Signature of the heatmap plotting function
+#[doc(hidden)]
+pub type HeatmapFn<T> = unsafe extern "C" fn(
+    *const c_char,
+    *const T,
+    i32,
+    i32,
+    f64,
+    f64,
+    *const c_char,
+    ImPlotPoint,
+    ImPlotPoint,
+);
+
+/// Numeric types that can be plotted directly: `i8`, `u8`, `i16`, `u16`, `i32`, `u32`, `i64`,
+/// `u64`, `f32` and `f64`. This trait is sealed, it can't be implemented for other types.
+pub trait PlotData: Copy + private::Sealed {
+    #[doc(hidden)]
+    const PLOT_LINE: XYFn<Self>;
+    #[doc(hidden)]
+    const PLOT_SCATTER: XYFn<Self>;
+    #[doc(hidden)]
+    const PLOT_STAIRS: XYFn<Self>;
+    #[doc(hidden)]
+    const PLOT_BARS: BarsFn<Self>;
+    #[doc(hidden)]
+    const PLOT_BARS_H: BarsFn<Self>;
+    #[doc(hidden)]
+    const PLOT_STEMS: StemsFn<Self>;
+    #[doc(hidden)]
+    const PLOT_STEMS_VALUES: StemsValuesFn<Self>;
+    #[doc(hidden)]
+    const PLOT_SHADED: ShadedFn<Self>;
+    #[doc(hidden)]
+    const PLOT_SHADED_BETWEEN: ShadedBetweenFn<Self>;
+    #[doc(hidden)]
+    const PLOT_HEATMAP: HeatmapFn<Self>;
+
+    /// Convert the value to a double, which is what ImPlot uses for plot coordinates
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_plot_data {
+    (
+        $data_type:ty {
+            line: $line:ident,
+            scatter: $scatter:ident,
+            stairs: $stairs:ident,
+            bars: $bars:ident,
+            bars_h: $bars_h:ident,
+            stems: $stems:ident,
+            stems_values: $stems_values:ident,
+            shaded: $shaded:ident,
+            shaded_between: $shaded_between:ident,
+            heatmap: $heatmap:ident,
+        }
+    ) => {
+        impl private::Sealed for $data_type {}
+
+        impl PlotData for $data_type {
+            const PLOT_LINE: XYFn<Self> = sys::$line;
+            const PLOT_SCATTER: XYFn<Self> = sys::$scatter;
+            const PLOT_STAIRS: XYFn<Self> = sys::$stairs;
+            const PLOT_BARS: BarsFn<Self> = sys::$bars;
+            const PLOT_BARS_H: BarsFn<Self> = sys::$bars_h;
+            const PLOT_STEMS: StemsFn<Self> = sys::$stems;
+            const PLOT_STEMS_VALUES: StemsValuesFn<Self> = sys::$stems_values;
+            const PLOT_SHADED: ShadedFn<Self> = sys::$shaded;
+            const PLOT_SHADED_BETWEEN: ShadedBetweenFn<Self> = sys::$shaded_between;
+            const PLOT_HEATMAP: HeatmapFn<Self> = sys::$heatmap;
+
+            #[inline]
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+
+impl_plot_data!(i8 {
+    line: ImPlot_PlotLine_S8PtrS8Ptr,
+    scatter: ImPlot_PlotScatter_S8PtrS8Ptr,
+    stairs: ImPlot_PlotStairs_S8PtrS8Ptr,
+    bars: ImPlot_PlotBars_S8PtrS8Ptr,
+    bars_h: ImPlot_PlotBarsH_S8PtrS8Ptr,
+    stems: ImPlot_PlotStems_S8PtrS8Ptr,
+    stems_values: ImPlot_PlotStems_S8PtrInt,
+    shaded: ImPlot_PlotShaded_S8PtrS8PtrInt,
+    shaded_between: ImPlot_PlotShaded_S8PtrS8PtrS8Ptr,
+    heatmap: ImPlot_PlotHeatmap_S8Ptr,
+});
+
+impl_plot_data!(u8 {
+    line: ImPlot_PlotLine_U8PtrU8Ptr,
+    scatter: ImPlot_PlotScatter_U8PtrU8Ptr,
+    stairs: ImPlot_PlotStairs_U8PtrU8Ptr,
+    bars: ImPlot_PlotBars_U8PtrU8Ptr,
+    bars_h: ImPlot_PlotBarsH_U8PtrU8Ptr,
+    stems: ImPlot_PlotStems_U8PtrU8Ptr,
+    stems_values: ImPlot_PlotStems_U8PtrInt,
+    shaded: ImPlot_PlotShaded_U8PtrU8PtrInt,
+    shaded_between: ImPlot_PlotShaded_U8PtrU8PtrU8Ptr,
+    heatmap: ImPlot_PlotHeatmap_U8Ptr,
+});
+
+impl_plot_data!(i16 {
+    line: ImPlot_PlotLine_S16PtrS16Ptr,
+    scatter: ImPlot_PlotScatter_S16PtrS16Ptr,
+    stairs: ImPlot_PlotStairs_S16PtrS16Ptr,
+    bars: ImPlot_PlotBars_S16PtrS16Ptr,
+    bars_h: ImPlot_PlotBarsH_S16PtrS16Ptr,
+    stems: ImPlot_PlotStems_S16PtrS16Ptr,
+    stems_values: ImPlot_PlotStems_S16PtrInt,
+    shaded: ImPlot_PlotShaded_S16PtrS16PtrInt,
+    shaded_between: ImPlot_PlotShaded_S16PtrS16PtrS16Ptr,
+    heatmap: ImPlot_PlotHeatmap_S16Ptr,
+});
+
+impl_plot_data!(u16 {
+    line: ImPlot_PlotLine_U16PtrU16Ptr,
+    scatter: ImPlot_PlotScatter_U16PtrU16Ptr,
+    stairs: ImPlot_PlotStairs_U16PtrU16Ptr,
+    bars: ImPlot_PlotBars_U16PtrU16Ptr,
+    bars_h: ImPlot_PlotBarsH_U16PtrU16Ptr,
+    stems: ImPlot_PlotStems_U16PtrU16Ptr,
+    stems_values: ImPlot_PlotStems_U16PtrInt,
+    shaded: ImPlot_PlotShaded_U16PtrU16PtrInt,
+    shaded_between: ImPlot_PlotShaded_U16PtrU16PtrU16Ptr,
+    heatmap: ImPlot_PlotHeatmap_U16Ptr,
+});
+
+impl_plot_data!(i32 {
+    line: ImPlot_PlotLine_S32PtrS32Ptr,
+    scatter: ImPlot_PlotScatter_S32PtrS32Ptr,
+    stairs: ImPlot_PlotStairs_S32PtrS32Ptr,
+    bars: ImPlot_PlotBars_S32PtrS32Ptr,
+    bars_h: ImPlot_PlotBarsH_S32PtrS32Ptr,
+    stems: ImPlot_PlotStems_S32PtrS32Ptr,
+    stems_values: ImPlot_PlotStems_S32PtrInt,
+    shaded: ImPlot_PlotShaded_S32PtrS32PtrInt,
+    shaded_between: ImPlot_PlotShaded_S32PtrS32PtrS32Ptr,
+    heatmap: ImPlot_PlotHeatmap_S32Ptr,
+});
+
+impl_plot_data!(u32 {
+    line: ImPlot_PlotLine_U32PtrU32Ptr,
+    scatter: ImPlot_PlotScatter_U32PtrU32Ptr,
+    stairs: ImPlot_PlotStairs_U32PtrU32Ptr,
+    bars: ImPlot_PlotBars_U32PtrU32Ptr,
+    bars_h: ImPlot_PlotBarsH_U32PtrU32Ptr,
+    stems: ImPlot_PlotStems_U32PtrU32Ptr,
+    stems_values: ImPlot_PlotStems_U32PtrInt,
+    shaded: ImPlot_PlotShaded_U32PtrU32PtrInt,
+    shaded_between: ImPlot_PlotShaded_U32PtrU32PtrU32Ptr,
+    heatmap: ImPlot_PlotHeatmap_U32Ptr,
+});
+
+impl_plot_data!(i64 {
+    line: ImPlot_PlotLine_S64PtrS64Ptr,
+    scatter: ImPlot_PlotScatter_S64PtrS64Ptr,
+    stairs: ImPlot_PlotStairs_S64PtrS64Ptr,
+    bars: ImPlot_PlotBars_S64PtrS64Ptr,
+    bars_h: ImPlot_PlotBarsH_S64PtrS64Ptr,
+    stems: ImPlot_PlotStems_S64PtrS64Ptr,
+    stems_values: ImPlot_PlotStems_S64PtrInt,
+    shaded: ImPlot_PlotShaded_S64PtrS64PtrInt,
+    shaded_between: ImPlot_PlotShaded_S64PtrS64PtrS64Ptr,
+    heatmap: ImPlot_PlotHeatmap_S64Ptr,
+});
+
+impl_plot_data!(u64 {
+    line: ImPlot_PlotLine_U64PtrU64Ptr,
+    scatter: ImPlot_PlotScatter_U64PtrU64Ptr,
+    stairs: ImPlot_PlotStairs_U64PtrU64Ptr,
+    bars: ImPlot_PlotBars_U64PtrU64Ptr,
+    bars_h: ImPlot_PlotBarsH_U64PtrU64Ptr,
+    stems: ImPlot_PlotStems_U64PtrU64Ptr,
+    stems_values: ImPlot_PlotStems_U64PtrInt,
+    shaded: ImPlot_PlotShaded_U64PtrU64PtrInt,
+    shaded_between: ImPlot_PlotShaded_U64PtrU64PtrU64Ptr,
+    heatmap: ImPlot_PlotHeatmap_U64Ptr,
+});
+
+impl_plot_data!(f32 {
+    line: ImPlot_PlotLine_FloatPtrFloatPtr,
+    scatter: ImPlot_PlotScatter_FloatPtrFloatPtr,
+    stairs: ImPlot_PlotStairs_FloatPtrFloatPtr,
+    bars: ImPlot_PlotBars_FloatPtrFloatPtr,
+    bars_h: ImPlot_PlotBarsH_FloatPtrFloatPtr,
+    stems: ImPlot_PlotStems_FloatPtrFloatPtr,
+    stems_values: ImPlot_PlotStems_FloatPtrInt,
+    shaded: ImPlot_PlotShaded_FloatPtrFloatPtrInt,
+    shaded_between: ImPlot_PlotShaded_FloatPtrFloatPtrFloatPtr,
+    heatmap: ImPlot_PlotHeatmap_FloatPtr,
+});
+
+impl_plot_data!(f64 {
+    line: ImPlot_PlotLine_doublePtrdoublePtr,
+    scatter: ImPlot_PlotScatter_doublePtrdoublePtr,
+    stairs: ImPlot_PlotStairs_doublePtrdoublePtr,
+    bars: ImPlot_PlotBars_doublePtrdoublePtr,
+    bars_h: ImPlot_PlotBarsH_doublePtrdoublePtr,
+    stems: ImPlot_PlotStems_doublePtrdoublePtr,
+    stems_values: ImPlot_PlotStems_doublePtrInt,
+    shaded: ImPlot_PlotShaded_doublePtrdoublePtrInt,
+    shaded_between: ImPlot_PlotShaded_doublePtrdoublePtrdoublePtr,
+    heatmap: ImPlot_PlotHeatmap_doublePtr,
+});
+
+/// View of plot data that is not laid out contiguously, such as one field of a slice of structs
+/// or every n-th element of an interleaved buffer. The view only ever covers elements inside the
+/// borrowed data, so plotting it can't read past the end of the buffer.
+///
+/// Plot elements take it in their `plot_strided` functions, which also accept an offset for
+/// plotting ring buffers. Note that ImPlot uses the same stride for all data arrays of a plot
+/// item, so for example the x and y views of a line need the same stride.
+#[derive(Debug)]
+pub struct StridedData<'a, T: PlotData> {
+    /// Pointer to the first element
+    first: *const T,
+    /// Number of elements in the view
+    count: usize,
+    /// Distance between two consecutive elements, in bytes
+    stride: usize,
+    /// Marker for the lifetime of the borrowed data
+    _data: PhantomData<&'a [T]>,
+}
+
+// Implemented by hand instead of derived, since deriving would add a bound on T, and on the
+// lifetime of the data.
+impl<'a, T: PlotData> Clone for StridedData<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: PlotData> Copy for StridedData<'a, T> {}
+
+impl<'a, T: PlotData> StridedData<'a, T> {
+    /// Internal constructor which checks that the stride can be passed on to ImPlot.
+    ///
+    /// # Panics
+    /// Will panic if the stride does not fit into an `i32`.
+    fn from_parts(first: *const T, count: usize, stride: usize) -> Self {
+        if i32::try_from(stride).is_err() {
+            panic!("Stride of {} bytes is too large for ImPlot", stride);
+        }
+        Self {
+            first,
+            count,
+            stride,
+            _data: PhantomData,
+        }
+    }
+
+    /// Create a view of contiguous data. This is what the plain `plot` functions of the plot
+    /// elements use.
+    pub fn new(data: &'a [T]) -> Self {
+        Self::from_parts(data.as_ptr(), data.len(), std::mem::size_of::<T>())
+    }
+
+    /// Create a view of every `step`-th element of `data`, starting at index `start`. For a
+    /// buffer of interleaved `[t, x, y, z]` records, `interleaved(&buffer, 1, 4)` gives the x
+    /// values. Any incomplete record at the end of the buffer is included as far as it has the
+    /// selected element.
+    ///
+    /// # Panics
+    /// Will panic if `step` is zero, or if the view has more than one element and the stride
+    /// does not fit into an `i32`.
+    pub fn interleaved(data: &'a [T], start: usize, step: usize) -> Self {
+        assert!(step > 0, "Step of interleaved data has to be at least one");
+        let count = if start < data.len() {
+            (data.len() - start - 1) / step + 1
+        } else {
+            0
+        };
+        // The stride is never used to get from one element to the next if there is at most one
+        // element, so the one of contiguous data is used, which also fits views created by `new`.
+        let stride = if count > 1 {
+            step.saturating_mul(std::mem::size_of::<T>())
+        } else {
+            std::mem::size_of::<T>()
+        };
+        Self::from_parts(data.as_ptr().wrapping_add(start), count, stride)
+    }
+
+    /// Create a view of one field of a slice of records, selected by the given projection,
+    /// for example `StridedData::from_records(&samples, |sample| &sample.x)`.
+    ///
+    /// # Panics
+    /// Will panic if the projection does not select the same field in every record, or if the
+    /// size of the records does not fit into an `i32`.
+    pub fn from_records<R, F: Fn(&R) -> &T>(records: &'a [R], field: F) -> Self {
+        let stride = std::mem::size_of::<R>();
+        let first = match records.first() {
+            Some(record) => field(record) as *const T,
+            None => return Self::from_parts(records.as_ptr() as *const T, 0, stride),
+        };
+        // The elements are read at a fixed distance from each other later on, so make sure
+        // this is where the projection actually points for every record.
+        records.iter().enumerate().for_each(|(index, record)| {
+            let expected = (first as *const u8).wrapping_add(index * stride);
+            if field(record) as *const T as *const u8 != expected {
+                panic!("Field projection has to select the same field in every record");
+            }
+        });
+        Self::from_parts(first, records.len(), stride)
+    }
+
+    /// Number of elements in the view
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether the view contains no elements
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Get the element at the given index, or `None` if the index is out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.count {
+            // This is in bounds, the constructors make sure that all elements of the view
+            // are inside the borrowed data.
+            Some(unsafe { *((self.first as *const u8).add(index * self.stride) as *const T) })
+        } else {
+            None
+        }
+    }
+
+    /// Internal helper function to get the pointer to the first element for ImPlot
+    pub(crate) fn as_ptr(&self) -> *const T {
+        self.first
+    }
+
+    /// Internal helper function to get the stride in bytes, as ImPlot expects it
+    pub(crate) fn stride(&self) -> i32 {
+        // The constructors make sure this fits
+        self.stride as i32
+    }
+}
+
+/// Internal helper function to convert a number of points to the `int` ImPlot takes. Counts that
+/// don't fit are clamped, which only means that fewer points are read.
+pub(crate) fn point_count(count: usize) -> i32 {
+    count.min(i32::MAX as usize) as i32
+}
+
+/// Internal helper function to get the number of points, offset and stride to pass to ImPlot
+/// for plotting the given views together. Returns `None` if there is nothing to plot.
+///
+/// # Panics
+/// Will panic if the views have different strides.
+pub(crate) fn strided_parameters<T: PlotData>(
+    views: &[StridedData<'_, T>],
+    offset: usize,
+) -> Option<(i32, i32, i32)> {
+    let stride = views[0].stride();
+    if views.iter().any(|view| view.stride() != stride) {
+        panic!("All data arrays of a plot item need to have the same stride");
+    }
+    // Limiting the count only means fewer points are read, so this is safe. ImPlot reads the
+    // element at (offset + index) % count, so the offset is reduced here to keep that sum from
+    // overflowing.
+    let count = views.iter().map(StridedData::len).min().unwrap_or(0);
+    let count = count.min(i32::MAX as usize);
+    if count == 0 {
+        None
+    } else {
+        Some((count as i32, (offset % count) as i32, stride))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy)]
+    struct Sample {
+        t: f64,
+        x: f64,
+        y: f32,
+    }
+
+    #[test]
+    fn test_strided_views_stay_in_bounds() {
+        let buffer = [0.0, 1.0, 2.0, 3.0, 10.0, 11.0, 12.0, 13.0, 20.0, 21.0];
+        let x = StridedData::interleaved(&buffer, 1, 4);
+        assert_eq!(x.len(), 3);
+        assert_eq!(
+            (x.get(0), x.get(2), x.get(3)),
+            (Some(1.0), Some(21.0), None)
+        );
+        assert!(StridedData::interleaved(&buffer, 10, 4).is_empty());
+
+        // Steps beyond the end of the data leave just the first element
+        let first = StridedData::interleaved(&buffer, 2, usize::MAX);
+        assert_eq!(
+            (first.len(), first.get(0), first.get(1)),
+            (1, Some(2.0), None)
+        );
+        assert_eq!(
+            strided_parameters(&[first, StridedData::new(&buffer[..1])], 0),
+            Some((1, 0, first.stride()))
+        );
+
+        let samples = [
+            Sample {
+                t: 0.0,
+                x: 0.5,
+                y: 1.5,
+            },
+            Sample {
+                t: 1.0,
+                x: 2.5,
+                y: 3.5,
+            },
+        ];
+        let t = StridedData::from_records(&samples, |sample| &sample.t);
+        let y = StridedData::from_records(&samples, |sample| &sample.y);
+        assert_eq!((t.get(1), y.get(0), y.get(2)), (Some(1.0), Some(1.5), None));
+        assert_eq!(strided_parameters(&[t, t], 5), Some((2, 1, t.stride())));
+        let none = StridedData::from_records(&samples[..0], |sample| &sample.t);
+        assert_eq!(strided_parameters(&[t, none], 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "same field in every record")]
+    fn test_records_projection_has_to_be_consistent() {
+        let samples = [
+            Sample {
+                t: 0.0,
+                x: 0.5,
+                y: 1.5,
+            },
+            Sample {
+                t: 1.0,
+                x: 2.5,
+                y: 3.5,
+            },
+        ];
+        StridedData::from_records(
+            &samples,
+            |sample| {
+                if sample.t > 0.0 {
+                    &sample.x
+                } else {
+                    &sample.t
+                }
+            },
+        );
+    }
+}
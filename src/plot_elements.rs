@@ -3,11 +3,99 @@
 //! This module defines the various structs that can be used for drawing different things such
 //! as lines, bars, scatter plots and text in a plot. For the module to create plots themselves,
 //! see `plot`.
-use crate::sys;
+use crate::{
+    plot_data::{point_count, strided_parameters},
+    sys, PlotData, StridedData, IMPLOT_AUTO, IMPLOT_AUTO_COL,
+};
+use imgui::TextureId;
+use std::any::Any;
 use std::ffi::CString;
-use std::os::raw::c_char;
+use std::os::raw::{c_char, c_void};
+use std::panic::{self, AssertUnwindSafe};
 
 pub use crate::sys::ImPlotPoint;
+use crate::sys::{ImPlotRange, ImPlotRect};
+
+// --- Getter support ----------------------------------------------------------------------------
+/// Internally-used struct for passing a closure through ImPlot's getter interface. If the
+/// closure panics, the panic is kept here until the plotting function has returned.
+struct GetterData<F> {
+    getter: F,
+    panic: Option<Box<dyn Any + Send + 'static>>,
+}
+
+/// Internal helper function to call the closure of a `GetterData` for the given index. Unwinding
+/// into the C++ code is undefined behaviour, so panics are caught here. Once the closure has
+/// panicked, it is not called again and the remaining points are NaN, which ImPlot skips.
+fn call_getter<F: Fn(usize) -> ImPlotPoint>(data: &mut GetterData<F>, idx: i32) -> ImPlotPoint {
+    let nan_point = ImPlotPoint {
+        x: f64::NAN,
+        y: f64::NAN,
+    };
+    if data.panic.is_some() {
+        return nan_point;
+    }
+    let getter = &data.getter;
+    // ImPlot only calls getters with indices between zero and the count, so this is safe.
+    match panic::catch_unwind(AssertUnwindSafe(|| getter(idx as usize))) {
+        Ok(point) => point,
+        Err(payload) => {
+            data.panic = Some(payload);
+            nan_point
+        }
+    }
+}
+
+/// Getter passing points from a closure to ImPlot. `data` has to point to a `GetterData<F>`.
+unsafe extern "C" fn point_getter<F: Fn(usize) -> ImPlotPoint>(
+    data: *mut c_void,
+    idx: i32,
+    point: *mut ImPlotPoint,
+) -> *mut c_void {
+    *point = call_getter(&mut *(data as *mut GetterData<F>), idx);
+    point as *mut c_void
+}
+
+/// Getter passing points from a closure to ImPlot, for the functions that take the getter
+/// variant returning the point. `data` has to point to a `GetterData<F>`.
+unsafe extern "C" fn point_getter_by_value<F: Fn(usize) -> ImPlotPoint>(
+    data: *mut c_void,
+    idx: i32,
+) -> ImPlotPoint {
+    call_getter(&mut *(data as *mut GetterData<F>), idx)
+}
+
+/// Internal helper function to plot through a closure. `plot_function` gets the data pointer to
+/// pass to the getter returned by `getter_for`, and does the actual plotting. If the closure
+/// panicked during plotting, the panic is resumed once the plotting function has returned.
+fn plot_with_getter<F, G, P>(getter: F, getter_for: fn(&F) -> G, plot_function: P)
+where
+    F: Fn(usize) -> ImPlotPoint,
+    P: FnOnce(G, *mut c_void),
+{
+    let getter_function = getter_for(&getter);
+    let mut data = GetterData {
+        getter,
+        panic: None,
+    };
+    plot_function(
+        getter_function,
+        &mut data as *mut GetterData<F> as *mut c_void,
+    );
+    if let Some(payload) = data.panic {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Internal helper function to get the getter for a closure, for `plot_with_getter`
+fn getter_of<F: Fn(usize) -> ImPlotPoint>(_: &F) -> sys::ImPlotPoint_getter {
+    Some(point_getter::<F>)
+}
+
+/// Internal helper function to get the by-value getter for a closure, for `plot_with_getter`
+fn getter_by_value_of<F: Fn(usize) -> ImPlotPoint>(_: &F) -> sys::ImPlotGetter {
+    Some(point_getter_by_value::<F>)
+}
 
 // --- Actual plotting functionality -------------------------------------------------------------
 /// Struct to provide functionality for plotting a line in a plot.
@@ -28,37 +116,222 @@ impl PlotLine {
         }
     }
 
-    /// Plot a line. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: &[f64], y: &[f64]) {
+    /// Plot a line. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build).
+    /// Any of the types implementing [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_strided(StridedData::new(x), StridedData::new(y), 0);
+    }
+
+    /// Plot a line from strided views of the data, see [`StridedData`]. Plotting starts at the
+    /// element with index `offset` and wraps around at the end of the data, which is useful for
+    /// ring buffers.
+    ///
+    /// # Panics
+    /// Will panic if the views of x and y have different strides.
+    pub fn plot_strided<T: PlotData>(&self, x: StridedData<T>, y: StridedData<T>, offset: usize) {
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
-            return;
-        }
+        let (count, offset, stride) = match strided_parameters(&[x, y], offset) {
+            Some(parameters) => parameters,
+            None => return,
+        };
         unsafe {
-            sys::ImPlot_PlotLine_doublePtrdoublePtr(
+            T::PLOT_LINE(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                count,
+                offset,
+                stride,
             );
         }
     }
 
-    pub fn plot_i64(&self, x: &[i64], y: &[i64]) {
+    /// Plot a line through points computed by the given closure, for example a function or
+    /// data that is not laid out in a slice. The closure is called with indices from 0 to
+    /// `count - 1`, possibly several times per frame. If it panics, the panic is passed on once
+    /// ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotLineG"))]
+    pub fn plot_getter<F: Fn(usize) -> ImPlotPoint>(&self, count: usize, getter: F) {
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
+        if count == 0 {
             return;
         }
+        plot_with_getter(getter, getter_of, |getter, data| unsafe {
+            sys::ImPlot_PlotLineG(
+                self.label.as_ptr() as *const c_char,
+                getter,
+                data,
+                point_count(count),
+            );
+        });
+    }
+}
+
+/// Struct to provide functionality for plotting shaded areas in a plot, either between a line
+/// and a reference y value, or between two lines.
+pub struct PlotShaded {
+    /// Label to show in the legend for this shaded area
+    label: CString,
+
+    /// Reference y value to shade towards when only one line is given. Defaults to 0.
+    /// `f64::INFINITY` and `f64::NEG_INFINITY` shade to the edge of the plot.
+    reference_y: f64,
+}
+
+impl PlotShaded {
+    /// Create a new shaded area to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            reference_y: 0.0, // Default value taken from C++ implot
+        }
+    }
+
+    /// Set the reference y value that [`PlotShaded::plot`] shades towards. Use
+    /// `f64::INFINITY` or `f64::NEG_INFINITY` to shade to the top or bottom edge of the plot.
+    pub fn with_reference_y(mut self, reference_y: f64) -> Self {
+        self.reference_y = reference_y;
+        self
+    }
+
+    /// Plot the area between the line given by `x` and `y` and the reference y value. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build). Any of the types
+    /// implementing [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, x: &[T], y: &[T]) {
+        let (x, y) = (StridedData::new(x), StridedData::new(y));
+        // If there is no data to plot, we stop here
+        let (count, offset, stride) = match strided_parameters(&[x, y], 0) {
+            Some(parameters) => parameters,
+            None => return,
+        };
         unsafe {
-            sys::ImPlot_PlotLine_S64PtrS64Ptr(
+            T::PLOT_SHADED(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
-                std::mem::size_of::<i64>() as i32, // Stride, set to one f64 for the standard use case
+                count,
+                self.reference_y,
+                offset,
+                stride,
+            );
+        }
+    }
+
+    /// Plot the area between two lines that share the x values `x`, with y values `y1` and `y2`
+    /// respectively. This is what confidence bands are usually drawn with. Use this in closures
+    /// passed to [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot_between<T: PlotData>(&self, x: &[T], y1: &[T], y2: &[T]) {
+        let (x, y1, y2) = (
+            StridedData::new(x),
+            StridedData::new(y1),
+            StridedData::new(y2),
+        );
+        // If there is no data to plot, we stop here
+        let (count, offset, stride) = match strided_parameters(&[x, y1, y2], 0) {
+            Some(parameters) => parameters,
+            None => return,
+        };
+        unsafe {
+            T::PLOT_SHADED_BETWEEN(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y1.as_ptr(),
+                y2.as_ptr(),
+                count,
+                offset,
+                stride,
+            );
+        }
+    }
+
+    /// Plot the area between two lines through points computed by the given closures. Both
+    /// closures are called with indices from 0 to `count - 1`, possibly several times per frame.
+    /// If one of them panics, the panic is passed on once ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotShadedG"))]
+    pub fn plot_between_getters<F1, F2>(&self, count: usize, getter1: F1, getter2: F2)
+    where
+        F1: Fn(usize) -> ImPlotPoint,
+        F2: Fn(usize) -> ImPlotPoint,
+    {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        plot_with_getter(getter1, getter_of, |getter1, data1| {
+            plot_with_getter(getter2, getter_of, |getter2, data2| unsafe {
+                sys::ImPlot_PlotShadedG(
+                    self.label.as_ptr() as *const c_char,
+                    getter1,
+                    data1,
+                    getter2,
+                    data2,
+                    point_count(count),
+                );
+            });
+        });
+    }
+}
+
+/// Struct to provide functionality for plotting infinite lines, i.e. vertical lines at given x
+/// positions or horizontal lines at given y positions that span the whole plot. Useful for
+/// marking events or thresholds.
+pub struct PlotInfLines {
+    /// Label to show in the legend for these lines
+    label: CString,
+
+    /// Horizontal line mode
+    horizontal: bool,
+}
+
+impl PlotInfLines {
+    /// Create new infinite lines to be plotted. Defaults to vertical lines. Does not draw
+    /// anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            horizontal: false,
+        }
+    }
+
+    /// Set the lines to be horizontal, i.e. at y positions (default is vertical)
+    pub fn with_horizontal_lines(mut self) -> Self {
+        self.horizontal = true;
+        self
+    }
+
+    /// Draw one line at each of the given positions, which are x values for vertical and y
+    /// values for horizontal lines. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotVLines"))]
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHLines"))]
+    pub fn plot(&self, positions: &[f64]) {
+        // If there is no data to plot, we stop here
+        if positions.is_empty() {
+            return;
+        }
+        unsafe {
+            // C++ implot has separate functions for the two variants, with the same interface
+            let plot_function = if self.horizontal {
+                sys::ImPlot_PlotHLines_doublePtr
+                    as unsafe extern "C" fn(*const c_char, *const f64, i32, i32, i32)
+            } else {
+                sys::ImPlot_PlotVLines_doublePtr
+            };
+
+            plot_function(
+                self.label.as_ptr() as *const c_char,
+                positions.as_ptr(),
+                point_count(positions.len()),
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
             );
         }
     }
@@ -83,23 +356,54 @@ impl PlotStairs {
     }
 
     /// Plot a stairs style line. Use this in closures passed to
-    /// [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: &[f64], y: &[f64]) {
+    /// [`Plot::build()`](struct.Plot.html#method.build). Any of the types implementing
+    /// [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_strided(StridedData::new(x), StridedData::new(y), 0);
+    }
+
+    /// Plot a stairs style line from strided views of the data, see [`StridedData`]. Plotting starts at the
+    /// element with index `offset` and wraps around at the end of the data, which is useful for
+    /// ring buffers.
+    ///
+    /// # Panics
+    /// Will panic if the views of x and y have different strides.
+    pub fn plot_strided<T: PlotData>(&self, x: StridedData<T>, y: StridedData<T>, offset: usize) {
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
-            return;
-        }
+        let (count, offset, stride) = match strided_parameters(&[x, y], offset) {
+            Some(parameters) => parameters,
+            None => return,
+        };
         unsafe {
-            sys::ImPlot_PlotStairs_doublePtrdoublePtr(
+            T::PLOT_STAIRS(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                count,
+                offset,
+                stride,
             );
         }
     }
+
+    /// Plot a stairs style line through points computed by the given closure. Like for
+    /// [`PlotLine::plot_getter`], the closure gets indices from 0 to `count - 1` and its panics
+    /// are passed on once ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotStairsG"))]
+    pub fn plot_getter<F: Fn(usize) -> ImPlotPoint>(&self, count: usize, getter: F) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        plot_with_getter(getter, getter_by_value_of, |getter, data| unsafe {
+            sys::ImPlot_PlotStairsG(
+                self.label.as_ptr() as *const c_char,
+                getter,
+                data,
+                point_count(count),
+            );
+        });
+    }
 }
 
 /// Struct to provide functionality for creating a scatter plot
@@ -121,23 +425,54 @@ impl PlotScatter {
     }
 
     /// Draw a previously-created scatter plot. Use this in closures passed to
-    /// [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: &[f64], y: &[f64]) {
+    /// [`Plot::build()`](struct.Plot.html#method.build). Any of the types implementing
+    /// [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, x: &[T], y: &[T]) {
+        self.plot_strided(StridedData::new(x), StridedData::new(y), 0);
+    }
+
+    /// Draw a scatter plot from strided views of the data, see [`StridedData`]. Plotting starts at the
+    /// element with index `offset` and wraps around at the end of the data, which is useful for
+    /// ring buffers.
+    ///
+    /// # Panics
+    /// Will panic if the views of x and y have different strides.
+    pub fn plot_strided<T: PlotData>(&self, x: StridedData<T>, y: StridedData<T>, offset: usize) {
         // If there is no data to plot, we stop here
-        if x.len().min(y.len()) == 0 {
-            return;
-        }
+        let (count, offset, stride) = match strided_parameters(&[x, y], offset) {
+            Some(parameters) => parameters,
+            None => return,
+        };
         unsafe {
-            sys::ImPlot_PlotScatter_doublePtrdoublePtr(
+            T::PLOT_SCATTER(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                x.len().min(y.len()) as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                0,                           // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                count,
+                offset,
+                stride,
             );
         }
     }
+
+    /// Draw a scatter plot of points computed by the given closure. Like for
+    /// [`PlotLine::plot_getter`], the closure gets indices from 0 to `count - 1` and its panics
+    /// are passed on once ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotScatterG"))]
+    pub fn plot_getter<F: Fn(usize) -> ImPlotPoint>(&self, count: usize, getter: F) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        plot_with_getter(getter, getter_of, |getter, data| unsafe {
+            sys::ImPlot_PlotScatterG(
+                self.label.as_ptr() as *const c_char,
+                getter,
+                data,
+                point_count(count),
+            );
+        });
+    }
 }
 
 /// Struct to provide bar plotting functionality.
@@ -182,132 +517,455 @@ impl PlotBars {
     /// Draw a previously-created bar plot. Use this in closures passed to
     /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions`
     /// specify where on the corresponding axis (X for vertical mode, Y for horizontal mode) the
-    /// bar is drawn, and the `bar_values` specify what values the bars have.
-    pub fn plot(&self, axis_positions: &[f64], bar_values: &[f64]) {
-        let number_of_points = axis_positions.len().min(bar_values.len());
+    /// bar is drawn, and the `bar_values` specify what values the bars have. Any of the types
+    /// implementing [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, axis_positions: &[T], bar_values: &[T]) {
+        self.plot_strided(
+            StridedData::new(axis_positions),
+            StridedData::new(bar_values),
+            0,
+        );
+    }
+
+    /// Draw a previously-created bar plot from strided views of the data, see [`StridedData`].
+    /// Plotting starts at the element with index `offset` and wraps around at the end of the
+    /// data, which is useful for ring buffers.
+    ///
+    /// # Panics
+    /// Will panic if the views of the positions and values have different strides.
+    pub fn plot_strided<T: PlotData>(
+        &self,
+        axis_positions: StridedData<T>,
+        bar_values: StridedData<T>,
+        offset: usize,
+    ) {
         // If there is no data to plot, we stop here
-        if number_of_points == 0 {
-            return;
-        }
+        let (count, offset, stride) =
+            match strided_parameters(&[axis_positions, bar_values], offset) {
+                Some(parameters) => parameters,
+                None => return,
+            };
         unsafe {
             // C++ implot has separate functions for the two variants, but the interfaces
             // are the same, so they are unified here. The x and y values have different
             // meanings though, hence the swapping around before they are passed to the
             // plotting function.
-            let (plot_function, x, y);
-            if self.horizontal_bars {
-                plot_function = sys::ImPlot_PlotBarsH_doublePtrdoublePtr
-                    as unsafe extern "C" fn(
-                        *const c_char,
-                        *const f64,
-                        *const f64,
-                        i32,
-                        f64,
-                        i32,
-                        i32,
-                    );
-                x = bar_values;
-                y = axis_positions;
+            let (plot_function, x, y) = if self.horizontal_bars {
+                (T::PLOT_BARS_H, bar_values, axis_positions)
             } else {
-                plot_function = sys::ImPlot_PlotBars_doublePtrdoublePtr
-                    as unsafe extern "C" fn(
-                        *const c_char,
-                        *const f64,
-                        *const f64,
-                        i32,
-                        f64,
-                        i32,
-                        i32,
-                    );
-                x = axis_positions;
-                y = bar_values;
+                (T::PLOT_BARS, axis_positions, bar_values)
             };
 
             plot_function(
                 self.label.as_ptr() as *const c_char,
                 x.as_ptr(),
                 y.as_ptr(),
-                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
+                count,
                 self.bar_width,
-                0,                                 // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+                offset,
+                stride,
             );
         }
     }
+
+    /// Draw a bar plot from points computed by the given closure. For vertical bars, the x
+    /// coordinate of a point is the position of the bar and the y coordinate is its value, for
+    /// horizontal bars it's the other way around. The closure is called with indices from 0 to
+    /// `count - 1`, and panics in it are passed on once ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotBarsG"))]
+    pub fn plot_getter<F: Fn(usize) -> ImPlotPoint>(&self, count: usize, getter: F) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        let plot_function = if self.horizontal_bars {
+            sys::ImPlot_PlotBarsHG
+                as unsafe extern "C" fn(
+                    *const c_char,
+                    sys::ImPlotPoint_getter,
+                    *mut c_void,
+                    i32,
+                    f64,
+                )
+        } else {
+            sys::ImPlot_PlotBarsG
+        };
+        plot_with_getter(getter, getter_of, |getter, data| unsafe {
+            plot_function(
+                self.label.as_ptr() as *const c_char,
+                getter,
+                data,
+                point_count(count),
+                self.bar_width,
+            );
+        });
+    }
 }
 
-/// Struct to provide functionality for adding text within a plot
-pub struct PlotText {
-    /// Label to show in plot
-    label: CString,
+/// Struct to provide functionality for grouped bar charts, where each group contains one bar
+/// per item. The bars of a group are either placed next to each other or stacked.
+pub struct PlotBarGroups {
+    /// Labels of the items, shown in the legend. Stored as CStrings because that's what the
+    /// plot call needs, the pointer array for the C++ code is created when plotting.
+    labels: Vec<CString>,
 
-    /// X component of the pixel offset to be used. Will be used independently of the actual plot
-    /// scaling. Defaults to 0.
-    pixel_offset_x: f32,
+    /// Width of each group, in plot coordinate terms
+    group_width: f64,
 
-    /// Y component of the pixel offset to be used. Will be used independently of the actual plot
-    /// scaling. Defaults to 0.
-    pixel_offset_y: f32,
+    /// Position of the first group, the others follow at distances of one
+    shift: f64,
+
+    /// Horizontal bar mode
+    horizontal_bars: bool,
+
+    /// Whether the bars of a group are stacked instead of placed next to each other
+    stacked: bool,
 }
 
-impl PlotText {
-    /// Create a new text label to be shown. Does not draw anything yet.
+impl PlotBarGroups {
+    /// Create new bar groups to be shown, with one item per label. Defaults to drawing vertical
+    /// bars next to each other. Does not draw anything yet.
     ///
     /// # Panics
-    /// Will panic if the label string contains internal null bytes.
-    pub fn new(label: &str) -> Self {
+    /// Will panic if any of the label strings contain internal null bytes.
+    pub fn new(labels: &[&str]) -> Self {
         Self {
-            label: CString::new(label)
-                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
-            pixel_offset_x: 0.0,
-            pixel_offset_y: 0.0,
+            labels: labels
+                .iter()
+                .map(|label| {
+                    CString::new(*label).unwrap_or_else(|_| {
+                        panic!("Label string has internal null bytes: {}", label)
+                    })
+                })
+                .collect(),
+            group_width: 0.67, // Default value taken from C++ implot
+            shift: 0.0,
+            horizontal_bars: false,
+            stacked: false,
         }
     }
 
-    /// Add a pixel offset to the text to be plotted. This offset will be independent of the
-    /// scaling of the plot itself.
-    pub fn with_pixel_offset(mut self, offset_x: f32, offset_y: f32) -> Self {
-        self.pixel_offset_x = offset_x;
-        self.pixel_offset_y = offset_y;
+    /// Set the width of each group
+    pub fn with_group_width(mut self, group_width: f64) -> Self {
+        self.group_width = group_width;
         self
     }
 
-    /// Draw the text label in the plot at the given position, optionally vertically. Use this in
-    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
-    pub fn plot(&self, x: f64, y: f64, vertical: bool) {
-        // If there is nothing to show, don't do anything
-        if self.label.as_bytes().is_empty() {
+    /// Set the position of the first group on the axis, the others follow at distances of one
+    pub fn with_shift(mut self, shift: f64) -> Self {
+        self.shift = shift;
+        self
+    }
+
+    /// Set the bars to be horizontal (default is vertical)
+    pub fn with_horizontal_bars(mut self) -> Self {
+        self.horizontal_bars = true;
+        self
+    }
+
+    /// Stack the bars of each group on top of each other instead of placing them next to each
+    /// other
+    pub fn with_stacked_bars(mut self) -> Self {
+        self.stacked = true;
+        self
+    }
+
+    /// Draw the bar groups. The `values` are in row-major order with one row per item, i.e.
+    /// they contain the values of all groups for the first item, then for the second, and so on.
+    /// Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the number of values is not the number of items times `group_count`, or if
+    /// there are more values than fit in an `i32`.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotBarGroups"))]
+    pub fn plot(&self, values: &[f64], group_count: u32) {
+        let item_count = self.labels.len();
+        assert!(
+            item_count.checked_mul(group_count as usize) == Some(values.len()),
+            "Number of values does not match number of items times number of groups"
+        );
+        // ImPlot indexes the values with an int, so all of them have to be addressable by one.
+        // If there are values to plot, both counts are at most their number and fit as well.
+        assert!(
+            values.len() <= i32::MAX as usize,
+            "Too many bar group values to plot"
+        );
+        // If there is no data to plot, we stop here
+        if values.is_empty() {
             return;
         }
-
+        // The pointers point into the CStrings, which live as long as self
+        let label_pointers: Vec<*const c_char> =
+            self.labels.iter().map(|label| label.as_ptr()).collect();
+        let flags = if self.stacked {
+            sys::ImPlotBarGroupsFlags__ImPlotBarGroupsFlags_Stacked
+        } else {
+            sys::ImPlotBarGroupsFlags__ImPlotBarGroupsFlags_None
+        };
         unsafe {
-            sys::ImPlot_PlotText(
-                self.label.as_ptr() as *const c_char,
-                x,
-                y,
-                vertical,
-                sys::ImVec2 {
-                    x: self.pixel_offset_x,
-                    y: self.pixel_offset_y,
-                },
+            // C++ implot has separate functions for the two variants, with the same interface
+            let plot_function = if self.horizontal_bars {
+                sys::ImPlot_PlotBarGroupsH_doublePtr
+                    as unsafe extern "C" fn(
+                        *const *const c_char,
+                        *const f64,
+                        i32,
+                        i32,
+                        f64,
+                        f64,
+                        i32,
+                    )
+            } else {
+                sys::ImPlot_PlotBarGroups_doublePtr
+            };
+
+            plot_function(
+                label_pointers.as_ptr(),
+                values.as_ptr(),
+                item_count as i32,
+                group_count as i32,
+                self.group_width,
+                self.shift,
+                flags as sys::ImPlotBarGroupsFlags,
             );
         }
     }
 }
 
-/// Struct to provide functionality for creating headmaps.
-pub struct PlotHeatmap {
-    /// Label to show in plot
+/// Struct to provide functionality for plotting error bars, either vertically (errors in y) or
+/// horizontally (errors in x).
+pub struct PlotErrorBars {
+    /// Label to show in the legend for these error bars
     label: CString,
 
-    /// Scale range of the values shown. If this is set to `None`, the scale
-    /// is computed based on the values given to the `plot` function. If there
-    /// is a value, the tuple is interpreted as `(minimum, maximum)`.
-    scale_range: Option<(f64, f64)>,
+    /// Horizontal error bar mode
+    horizontal: bool,
 
-    /// Label C style format string, this is shown when a a value point is hovered.
-    /// None means don't show a label. The label is stored directly as an ImString because
-    /// that is what's needed for the plot call anyway. Conversion is done in the setter.
+    /// Color of the error bars, `None` means the automatic color
+    color: Option<[f32; 4]>,
+
+    /// Width of the whiskers at the ends of the error bars in pixels, `None` means the style
+    /// default
+    size: Option<f32>,
+
+    /// Line weight of the error bars, `None` means the style default
+    weight: Option<f32>,
+}
+
+impl PlotErrorBars {
+    /// Create new error bars to be shown. Defaults to vertical error bars. Does not draw
+    /// anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            horizontal: false,
+            color: None,
+            size: None,
+            weight: None,
+        }
+    }
+
+    /// Set the error bars to be horizontal, i.e. showing errors in x (default is vertical)
+    pub fn with_horizontal_error_bars(mut self) -> Self {
+        self.horizontal = true;
+        self
+    }
+
+    /// Set the color of the error bars, given as [red, green, blue, alpha] with components
+    /// between 0.0 and 1.0.
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the width of the whiskers at the ends of the error bars, in pixels
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Set the line weight of the error bars
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = Some(weight);
+        self
+    }
+
+    /// Draw error bars at the points given by `x` and `y`, extending by `err` in both
+    /// directions. Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotErrorBars"))]
+    pub fn plot(&self, x: &[f64], y: &[f64], err: &[f64]) {
+        let number_of_points = x.len().min(y.len()).min(err.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        self.set_style();
+        unsafe {
+            // C++ implot has separate functions for the two variants, with the same interface
+            let plot_function = if self.horizontal {
+                sys::ImPlot_PlotErrorBarsH_doublePtrdoublePtrdoublePtrInt
+                    as unsafe extern "C" fn(
+                        *const c_char,
+                        *const f64,
+                        *const f64,
+                        *const f64,
+                        i32,
+                        i32,
+                        i32,
+                    )
+            } else {
+                sys::ImPlot_PlotErrorBars_doublePtrdoublePtrdoublePtrInt
+            };
+
+            plot_function(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                err.as_ptr(),
+                point_count(number_of_points),
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    /// Draw error bars at the points given by `x` and `y`, extending by `neg` in negative and by
+    /// `pos` in positive direction. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotErrorBars"))]
+    pub fn plot_asymmetric(&self, x: &[f64], y: &[f64], neg: &[f64], pos: &[f64]) {
+        let number_of_points = x.len().min(y.len()).min(neg.len()).min(pos.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        self.set_style();
+        unsafe {
+            // C++ implot has separate functions for the two variants, with the same interface
+            let plot_function = if self.horizontal {
+                sys::ImPlot_PlotErrorBarsH_doublePtrdoublePtrdoublePtrdoublePtr
+                    as unsafe extern "C" fn(
+                        *const c_char,
+                        *const f64,
+                        *const f64,
+                        *const f64,
+                        *const f64,
+                        i32,
+                        i32,
+                        i32,
+                    )
+            } else {
+                sys::ImPlot_PlotErrorBars_doublePtrdoublePtrdoublePtrdoublePtr
+            };
+
+            plot_function(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                neg.as_ptr(),
+                pos.as_ptr(),
+                point_count(number_of_points),
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    /// Internal helper function to pass the error bar style on to ImPlot, if one was set. This
+    /// only affects the next item that is plotted.
+    fn set_style(&self) {
+        if self.color.is_none() && self.size.is_none() && self.weight.is_none() {
+            return;
+        }
+        let color = self
+            .color
+            .map_or(IMPLOT_AUTO_COL, |[x, y, z, w]| sys::ImVec4 { x, y, z, w });
+        unsafe {
+            sys::ImPlot_SetNextErrorBarStyle(
+                color,
+                self.size.unwrap_or(IMPLOT_AUTO as f32),
+                self.weight.unwrap_or(IMPLOT_AUTO as f32),
+            );
+        }
+    }
+}
+
+/// Struct to provide functionality for adding text within a plot
+pub struct PlotText {
+    /// Label to show in plot
+    label: CString,
+
+    /// X component of the pixel offset to be used. Will be used independently of the actual plot
+    /// scaling. Defaults to 0.
+    pixel_offset_x: f32,
+
+    /// Y component of the pixel offset to be used. Will be used independently of the actual plot
+    /// scaling. Defaults to 0.
+    pixel_offset_y: f32,
+}
+
+impl PlotText {
+    /// Create a new text label to be shown. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            pixel_offset_x: 0.0,
+            pixel_offset_y: 0.0,
+        }
+    }
+
+    /// Add a pixel offset to the text to be plotted. This offset will be independent of the
+    /// scaling of the plot itself.
+    pub fn with_pixel_offset(mut self, offset_x: f32, offset_y: f32) -> Self {
+        self.pixel_offset_x = offset_x;
+        self.pixel_offset_y = offset_y;
+        self
+    }
+
+    /// Draw the text label in the plot at the given position, optionally vertically. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    pub fn plot(&self, x: f64, y: f64, vertical: bool) {
+        // If there is nothing to show, don't do anything
+        if self.label.as_bytes().is_empty() {
+            return;
+        }
+
+        unsafe {
+            sys::ImPlot_PlotText(
+                self.label.as_ptr() as *const c_char,
+                x,
+                y,
+                vertical,
+                sys::ImVec2 {
+                    x: self.pixel_offset_x,
+                    y: self.pixel_offset_y,
+                },
+            );
+        }
+    }
+}
+
+/// Struct to provide functionality for creating headmaps.
+pub struct PlotHeatmap {
+    /// Label to show in plot
+    label: CString,
+
+    /// Scale range of the values shown. If this is set to `None`, the scale
+    /// is computed based on the values given to the `plot` function. If there
+    /// is a value, the tuple is interpreted as `(minimum, maximum)`.
+    scale_range: Option<(f64, f64)>,
+
+    /// Label C style format string, this is shown when a a value point is hovered.
+    /// None means don't show a label. The label is stored directly as an ImString because
+    /// that is what's needed for the plot call anyway. Conversion is done in the setter.
     label_format: Option<CString>,
 
     /// Lower left point for the bounding rectangle. This is called `bounds_min` in the C++ code.
@@ -367,21 +1025,39 @@ impl PlotHeatmap {
     }
 
     /// Plot the heatmap, with the given values (assumed to be in row-major order),
-    /// number of rows and number of columns.
-    pub fn plot(&self, values: &[f64], number_of_rows: u32, number_of_cols: u32) {
+    /// number of rows and number of columns. Any of the types implementing [`PlotData`] can be
+    /// used. Nothing is plotted if `values` is empty.
+    ///
+    /// # Panics
+    /// Will panic if there are fewer values than `number_of_rows * number_of_cols`.
+    pub fn plot<T: PlotData>(&self, values: &[T], number_of_rows: u32, number_of_cols: u32) {
+        if values.is_empty() {
+            return;
+        }
+        // ImPlot reads rows * cols values, which have to be there
+        assert!(
+            (number_of_rows as usize)
+                .checked_mul(number_of_cols as usize)
+                .map_or(false, |count| count <= values.len()),
+            "Heatmap of {} rows and {} columns needs more than the {} given values",
+            number_of_rows,
+            number_of_cols,
+            values.len()
+        );
+
         // If no range was given, determine that range
         let scale_range = self.scale_range.unwrap_or_else(|| {
-            let mut min_seen = values[0];
-            let mut max_seen = values[0];
+            let mut min_seen = values[0].to_f64();
+            let mut max_seen = values[0].to_f64();
             values.iter().for_each(|value| {
-                min_seen = min_seen.min(*value);
-                max_seen = max_seen.max(*value);
+                min_seen = min_seen.min(value.to_f64());
+                max_seen = max_seen.max(value.to_f64());
             });
             (min_seen, max_seen)
         });
 
         unsafe {
-            sys::ImPlot_PlotHeatmap_doublePtr(
+            T::PLOT_HEATMAP(
                 self.label.as_ptr() as *const c_char,
                 values.as_ptr(),
                 number_of_rows as i32, // Not sure why C++ code uses a signed value here
@@ -402,51 +1078,1247 @@ impl PlotHeatmap {
     }
 }
 
-/// Struct to provide stem plotting functionality.
-pub struct PlotStems {
-    /// Label to show in the legend for this line
-    label: CString,
+/// Struct to provide functionality for pie charts.
+pub struct PlotPieChart {
+    /// Labels of the slices, shown in the legend. Stored as CStrings because that's what the
+    /// plot call needs, the pointer array for the C++ code is created when plotting.
+    labels: Vec<CString>,
 
-    /// Reference value for the y value, which the stems are "with respect to"
-    reference_y: f64,
+    /// Whether to normalize the values so the slices make up a full circle even if the values
+    /// don't sum up to 1. Without this, values summing up to less than 1 leave a gap.
+    normalize: bool,
+
+    /// Label C style format string for the values shown on the slices. None means the values
+    /// are not shown.
+    label_format: Option<CString>,
+
+    /// Angle in degrees at which the first slice starts, counter-clockwise from the positive
+    /// x direction
+    start_angle: f64,
 }
 
-impl PlotStems {
-    /// Create a new stem plot to be shown. Does not draw anything by itself, call
-    /// [`PlotStems::plot`] on the struct for that.
-    pub fn new(label: &str) -> Self {
+impl PlotPieChart {
+    /// Create a new pie chart with one slice per label. Uses the same defaults as the C++
+    /// version. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if any of the label strings contain internal null bytes.
+    pub fn new(labels: &[&str]) -> Self {
         Self {
-            label: CString::new(label)
-                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
-            reference_y: 0.0, // Default value taken from C++ implot
+            labels: labels
+                .iter()
+                .map(|label| {
+                    CString::new(*label).unwrap_or_else(|_| {
+                        panic!("Label string has internal null bytes: {}", label)
+                    })
+                })
+                .collect(),
+            normalize: false,
+            label_format: Some(CString::new("%.1f").unwrap()),
+            start_angle: 90.0, // Default value taken from C++ implot
         }
     }
 
-    /// Set the reference y value for the stems
-    pub fn with_reference_y(mut self, reference_y: f64) -> Self {
-        self.reference_y = reference_y;
+    /// Set whether the values are normalized so the slices always make up a full circle
+    pub fn with_normalize(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
         self
     }
 
-    /// Draw a previously-created stem plot. Use this in closures passed to
-    /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions` specify where on the
-    /// X axis the stems are drawn, and the `stem_values` specify what values the stems have.
-    pub fn plot(&self, axis_positions: &[f64], stem_values: &[f64]) {
-        let number_of_points = axis_positions.len().min(stem_values.len());
+    /// Specify the label format for the values shown on the slices. `None` means no values are
+    /// shown.
+    ///
+    /// # Panics
+    /// Will panic if the label format string contains internal null bytes.
+    ///
+    /// # Safety
+    /// This function directly sets the format string of a C formatting function (`sprintf`). As
+    /// such, one has to check oneself that the format consumes exactly one `double` and that the
+    /// formatted numbers do not yield strings exceeding the length of the buffer used in the C++
+    /// code (32 bytes right now, this might change in the future, make sure to check in the
+    /// vendored-in C++ code to be sure). While the string is not used until later and hence the
+    /// function here is strictly speaking safe, the effect of this function can lead to
+    /// unsoundness later, hence it is marked as unsafe.
+    pub unsafe fn with_label_format(mut self, label_format: Option<&str>) -> Self {
+        self.label_format = label_format.map(|x| {
+            CString::new(x)
+                .unwrap_or_else(|_| panic!("Format label string has internal null bytes: {}", x))
+        });
+        self
+    }
+
+    /// Set the angle in degrees at which the first slice starts, counter-clockwise from the
+    /// positive x direction. Defaults to 90, i.e. the top of the chart.
+    pub fn with_start_angle(mut self, start_angle: f64) -> Self {
+        self.start_angle = start_angle;
+        self
+    }
+
+    /// Draw the pie chart with the given values, centered at (`x`, `y`) with the given radius,
+    /// all in plot coordinates. Values beyond the number of labels are ignored. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotPieChart"))]
+    pub fn plot(&self, values: &[f64], x: f64, y: f64, radius: f64) {
+        let number_of_slices = self.labels.len().min(values.len());
         // If there is no data to plot, we stop here
-        if number_of_points == 0 {
+        if number_of_slices == 0 {
             return;
         }
+        // The pointers point into the CStrings, which live as long as self
+        let label_pointers: Vec<*const c_char> =
+            self.labels.iter().map(|label| label.as_ptr()).collect();
         unsafe {
-            sys::ImPlot_PlotStems_doublePtrdoublePtr(
-                self.label.as_ptr() as *const c_char,
-                axis_positions.as_ptr(),
-                stem_values.as_ptr(),
-                number_of_points as i32, // "as" casts saturate as of Rust 1.45. This is safe here.
-                self.reference_y,
-                0,                                 // No offset
-                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            sys::ImPlot_PlotPieChart_doublePtr(
+                label_pointers.as_ptr(),
+                values.as_ptr(),
+                point_count(number_of_slices),
+                x,
+                y,
+                radius,
+                self.normalize,
+                self.label_format
+                    .as_ref()
+                    .map_or(std::ptr::null(), |format| format.as_ptr()),
+                self.start_angle,
+            );
+        }
+    }
+}
+
+/// Choice of bins for histograms, either an explicit number of bins or one of the methods to
+/// determine the number of bins automatically from the data.
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotBin"))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bins {
+    /// Explicit number of bins. Has to be at least one.
+    Count(u32),
+    /// k = sqrt(n)
+    Sqrt,
+    /// k = 1 + log2(n)
+    Sturges,
+    /// k = 2 * cbrt(n)
+    Rice,
+    /// w = 3.49 * sigma / cbrt(n)
+    Scott,
+}
+
+impl Bins {
+    /// Internal helper function to convert the bins choice to what ImPlot expects, which is
+    /// either a positive number of bins or one of the negative `ImPlotBin` values.
+    ///
+    /// # Panics
+    /// Will panic if an explicit number of bins is zero.
+    fn to_raw(self) -> i32 {
+        match self {
+            Bins::Count(count) => {
+                assert!(count > 0, "Histograms need at least one bin");
+                count.min(i32::MAX as u32) as i32
+            }
+            Bins::Sqrt => sys::ImPlotBin__ImPlotBin_Sqrt,
+            Bins::Sturges => sys::ImPlotBin__ImPlotBin_Sturges,
+            Bins::Rice => sys::ImPlotBin__ImPlotBin_Rice,
+            Bins::Scott => sys::ImPlotBin__ImPlotBin_Scott,
+        }
+    }
+}
+
+/// Struct to provide functionality for histograms of one-dimensional data.
+pub struct PlotHistogram {
+    /// Label to show in the legend for this histogram
+    label: CString,
+
+    /// Choice of bins
+    bins: Bins,
+
+    /// Whether each bin contains its count plus the counts of all previous bins
+    cumulative: bool,
+
+    /// Whether the counts are normalized to form a probability density function
+    density: bool,
+
+    /// Range of values to sort into bins. `None` means the range of the data.
+    range: Option<ImPlotRange>,
+
+    /// Whether values outside of the range are counted towards normalization and the
+    /// cumulative counts
+    outliers: bool,
+
+    /// Width of the bars relative to the bin width
+    bar_scale: f64,
+}
+
+impl PlotHistogram {
+    /// Create a new histogram to be shown. Uses the same defaults as the C++ version (see code
+    /// for what those are). Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            bins: Bins::Sturges,
+            cumulative: false,
+            density: false,
+            range: None,
+            outliers: true,
+            bar_scale: 1.0,
+        }
+    }
+
+    /// Set the bins, see `Bins` for the available choices
+    pub fn with_bins(mut self, bins: Bins) -> Self {
+        self.bins = bins;
+        self
+    }
+
+    /// Set whether each bin contains its count plus the counts of all previous bins
+    pub fn with_cumulative(mut self, cumulative: bool) -> Self {
+        self.cumulative = cumulative;
+        self
+    }
+
+    /// Set whether the counts are normalized to form a probability density function
+    pub fn with_density(mut self, density: bool) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Set the range of values to sort into bins. Without this, the range of the data is used.
+    pub fn with_range<R: Into<ImPlotRange>>(mut self, range: R) -> Self {
+        self.range = Some(range.into());
+        self
+    }
+
+    /// Set whether values outside of the range are counted towards normalization and the
+    /// cumulative counts. Only has an effect if a range is set. Defaults to true.
+    pub fn with_outliers(mut self, outliers: bool) -> Self {
+        self.outliers = outliers;
+        self
+    }
+
+    /// Set the width of the bars relative to the bin width. Defaults to 1.
+    pub fn with_bar_scale(mut self, bar_scale: f64) -> Self {
+        self.bar_scale = bar_scale;
+        self
+    }
+
+    /// Draw the histogram of the given values. Returns the largest bin value, or 0 if there
+    /// were no values. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if an explicit number of zero bins was set.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHistogram"))]
+    pub fn plot(&self, values: &[f64]) -> f64 {
+        // If there is no data to plot, we stop here
+        if values.is_empty() {
+            return 0.0;
+        }
+        unsafe {
+            sys::ImPlot_PlotHistogram_doublePtr(
+                self.label.as_ptr() as *const c_char,
+                values.as_ptr(),
+                point_count(values.len()),
+                self.bins.to_raw(),
+                self.cumulative,
+                self.density,
+                // An empty range means "use the range of the data" in the C++ code
+                self.range.unwrap_or(ImPlotRange { Min: 0.0, Max: 0.0 }),
+                self.outliers,
+                self.bar_scale,
+            )
+        }
+    }
+}
+
+/// Struct to provide functionality for histograms of two-dimensional data, drawn like a heatmap.
+pub struct PlotHistogram2D {
+    /// Label to show in the legend for this histogram
+    label: CString,
+
+    /// Choice of bins in x direction
+    x_bins: Bins,
+
+    /// Choice of bins in y direction
+    y_bins: Bins,
+
+    /// Whether the counts are normalized to form a probability density function
+    density: bool,
+
+    /// Range of values to sort into bins. `None` means the range of the data.
+    range: Option<ImPlotRect>,
+
+    /// Whether values outside of the range are counted towards normalization
+    outliers: bool,
+}
+
+impl PlotHistogram2D {
+    /// Create a new 2D histogram to be shown. Uses the same defaults as the C++ version (see
+    /// code for what those are). Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            x_bins: Bins::Count(10),
+            y_bins: Bins::Count(10),
+            density: false,
+            range: None,
+            outliers: true,
+        }
+    }
+
+    /// Set the bins in x and y direction, see `Bins` for the available choices
+    pub fn with_bins(mut self, x_bins: Bins, y_bins: Bins) -> Self {
+        self.x_bins = x_bins;
+        self.y_bins = y_bins;
+        self
+    }
+
+    /// Set whether the counts are normalized to form a probability density function
+    pub fn with_density(mut self, density: bool) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Set the range of values to sort into bins. Without this, the range of the data is used.
+    pub fn with_range(mut self, range: ImPlotRect) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Set whether values outside of the range are counted towards normalization. Only has an
+    /// effect if a range is set. Defaults to true.
+    pub fn with_outliers(mut self, outliers: bool) -> Self {
+        self.outliers = outliers;
+        self
+    }
+
+    /// Draw the histogram of the points given by `x` and `y`. Returns the largest bin value,
+    /// or 0 if there were no points, which is useful for setting up a matching colormap scale.
+    /// Use this in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if an explicit number of zero bins was set.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotHistogram2D"))]
+    pub fn plot(&self, x: &[f64], y: &[f64]) -> f64 {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return 0.0;
+        }
+        unsafe {
+            sys::ImPlot_PlotHistogram2D_doublePtr(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                point_count(number_of_points),
+                self.x_bins.to_raw(),
+                self.y_bins.to_raw(),
+                self.density,
+                // An empty range means "use the range of the data" in the C++ code
+                self.range.unwrap_or(ImPlotRect {
+                    X: ImPlotRange { Min: 0.0, Max: 0.0 },
+                    Y: ImPlotRange { Min: 0.0, Max: 0.0 },
+                }),
+                self.outliers,
+            )
+        }
+    }
+}
+
+/// Struct to provide functionality for digital (logic analyzer style) plots. Digital plots are
+/// not affected by the Y axis limits, they are stacked at the bottom of the plot instead, with
+/// the height and gap of each channel set by `StyleVar::DigitalBitHeight` and
+/// `StyleVar::DigitalBitGap`.
+pub struct PlotDigital {
+    /// Label to show in the legend for this channel
+    label: CString,
+}
+
+/// Internally-used struct for passing boolean digital data through ImPlot's getter interface
+struct DigitalBoolData<'a> {
+    x: &'a [f64],
+    y: &'a [bool],
+}
+
+/// Getter for boolean digital data, converting the values to 0/1 on the fly. `data` has to
+/// point to a `DigitalBoolData` and `idx` has to be in range for both of its slices.
+unsafe extern "C" fn digital_bool_getter(
+    data: *mut std::os::raw::c_void,
+    idx: i32,
+    point: *mut ImPlotPoint,
+) -> *mut std::os::raw::c_void {
+    let data = &*(data as *const DigitalBoolData);
+    let idx = idx as usize;
+    *point = ImPlotPoint {
+        x: *data.x.get_unchecked(idx),
+        y: if *data.y.get_unchecked(idx) { 1.0 } else { 0.0 },
+    };
+    point as *mut std::os::raw::c_void
+}
+
+impl PlotDigital {
+    /// Create a new digital channel to be plotted. Does not draw anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+        }
+    }
+
+    /// Plot a digital channel. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigital"))]
+    pub fn plot(&self, x: &[f64], y: &[f64]) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        unsafe {
+            sys::ImPlot_PlotDigital_doublePtr(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                point_count(number_of_points),
+                0,                                 // No offset
+                std::mem::size_of::<f64>() as i32, // Stride, set to one f64 for the standard use case
+            );
+        }
+    }
+
+    /// Plot a digital channel with integer data, for example timestamps in nanoseconds.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigital"))]
+    pub fn plot_i64(&self, x: &[i64], y: &[i64]) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        unsafe {
+            sys::ImPlot_PlotDigital_S64Ptr(
+                self.label.as_ptr() as *const c_char,
+                x.as_ptr(),
+                y.as_ptr(),
+                point_count(number_of_points),
+                0,                                 // No offset
+                std::mem::size_of::<i64>() as i32, // Stride, set to one i64 for the standard use case
+            );
+        }
+    }
+
+    /// Plot a digital channel with boolean data, where `true` is shown as high and `false` as
+    /// low. The values are converted while plotting, so this does not allocate.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigitalG"))]
+    pub fn plot_bool(&self, x: &[f64], y: &[bool]) {
+        let number_of_points = x.len().min(y.len());
+        // If there is no data to plot, we stop here
+        if number_of_points == 0 {
+            return;
+        }
+        let mut data = DigitalBoolData { x, y };
+        unsafe {
+            sys::ImPlot_PlotDigitalG(
+                self.label.as_ptr() as *const c_char,
+                Some(digital_bool_getter),
+                &mut data as *mut DigitalBoolData as *mut std::os::raw::c_void,
+                point_count(number_of_points),
+            );
+        }
+    }
+
+    /// Plot a digital channel from points computed by the given closure, which is called with
+    /// indices from 0 to `count - 1`. The y coordinates are the values of the channel. Panics in
+    /// the closure are passed on once ImPlot is done with the item.
+    #[rustversion::attr(since(1.48), doc(alias = "PlotDigitalG"))]
+    pub fn plot_getter<F: Fn(usize) -> ImPlotPoint>(&self, count: usize, getter: F) {
+        // If there is no data to plot, we stop here
+        if count == 0 {
+            return;
+        }
+        plot_with_getter(getter, getter_of, |getter, data| unsafe {
+            sys::ImPlot_PlotDigitalG(
+                self.label.as_ptr() as *const c_char,
+                getter,
+                data,
+                point_count(count),
+            );
+        });
+    }
+}
+
+/// Struct to provide functionality for showing images (textures) in a plot, placed and scaled
+/// in plot coordinates.
+pub struct PlotImage {
+    /// Label to show in the legend for this image
+    label: CString,
+
+    /// Texture coordinates of the image corner placed at the lower bounds. Defaults to [0, 0].
+    uv0: [f32; 2],
+
+    /// Texture coordinates of the image corner placed at the upper bounds. Defaults to [1, 1].
+    uv1: [f32; 2],
+
+    /// Color the image is multiplied with, as [red, green, blue, alpha]. Defaults to white,
+    /// i.e. the image as it is.
+    tint: [f32; 4],
+}
+
+impl PlotImage {
+    /// Create a new image to be shown, covering the whole texture without tint. Does not draw
+    /// anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the label string contains internal null bytes.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            uv0: [0.0, 0.0],
+            uv1: [1.0, 1.0],
+            tint: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Set the part of the texture to show, as texture coordinates of the corners placed at the
+    /// lower and upper bounds respectively. Swapping coordinates flips the image.
+    pub fn with_uv(mut self, uv0: [f32; 2], uv1: [f32; 2]) -> Self {
+        self.uv0 = uv0;
+        self.uv1 = uv1;
+        self
+    }
+
+    /// Set the tint color the image is multiplied with, given as [red, green, blue, alpha] with
+    /// components between 0.0 and 1.0.
+    pub fn with_tint(mut self, tint: [f32; 4]) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Draw the image of the given texture, stretched to cover the rectangle between
+    /// `bounds_min` and `bounds_max` in plot coordinates. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "PlotImage"))]
+    pub fn plot(&self, texture_id: TextureId, bounds_min: ImPlotPoint, bounds_max: ImPlotPoint) {
+        let [tint_r, tint_g, tint_b, tint_a] = self.tint;
+        unsafe {
+            sys::ImPlot_PlotImage(
+                self.label.as_ptr() as *const c_char,
+                texture_id.id() as sys::ImTextureID,
+                bounds_min,
+                bounds_max,
+                sys::ImVec2 {
+                    x: self.uv0[0],
+                    y: self.uv0[1],
+                },
+                sys::ImVec2 {
+                    x: self.uv1[0],
+                    y: self.uv1[1],
+                },
+                sys::ImVec4 {
+                    x: tint_r,
+                    y: tint_g,
+                    z: tint_b,
+                    w: tint_a,
+                },
+            );
+        }
+    }
+}
+
+/// Struct to provide stem plotting functionality.
+pub struct PlotStems {
+    /// Label to show in the legend for this line
+    label: CString,
+
+    /// Reference value the stems are "with respect to". This is a y value for vertical and an
+    /// x value for horizontal stems.
+    reference: f64,
+
+    /// Distance between the positions of the stems, when only their values are given
+    position_scale: f64,
+
+    /// Position of the first stem, when only the values of the stems are given
+    position_start: f64,
+
+    /// Horizontal stem mode
+    horizontal: bool,
+}
+
+impl PlotStems {
+    /// Create a new stem plot to be shown. Does not draw anything by itself, call
+    /// [`PlotStems::plot`] on the struct for that.
+    pub fn new(label: &str) -> Self {
+        Self {
+            label: CString::new(label)
+                .unwrap_or_else(|_| panic!("Label string has internal null bytes: {}", label)),
+            reference: 0.0,      // Default value taken from C++ implot
+            position_scale: 1.0, // Default value taken from C++ implot
+            position_start: 0.0, // Default value taken from C++ implot
+            horizontal: false,
+        }
+    }
+
+    /// Set the reference y value for the stems. For horizontal stems, this is the reference
+    /// x value.
+    pub fn with_reference_y(mut self, reference_y: f64) -> Self {
+        self.reference = reference_y;
+        self
+    }
+
+    /// Set the distance between the stem positions and the position of the first stem, for
+    /// [`PlotStems::plot_values`]. The defaults are 1 and 0, i.e. the stems are at positions
+    /// 0, 1, 2 and so on.
+    pub fn with_positions(mut self, scale: f64, start: f64) -> Self {
+        self.position_scale = scale;
+        self.position_start = start;
+        self
+    }
+
+    /// Set the stems to be horizontal, i.e. reaching from the reference x value to the stem
+    /// values at the given y positions (default is vertical).
+    pub fn with_horizontal_stems(mut self) -> Self {
+        self.horizontal = true;
+        self
+    }
+
+    /// Draw a previously-created stem plot. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build). The `axis_positions` specify where on the
+    /// axis (X for vertical mode, Y for horizontal mode) the stems are drawn, and the
+    /// `stem_values` specify what values the stems have. Any of the types implementing
+    /// [`PlotData`] can be used.
+    pub fn plot<T: PlotData>(&self, axis_positions: &[T], stem_values: &[T]) {
+        self.plot_strided(
+            StridedData::new(axis_positions),
+            StridedData::new(stem_values),
+            0,
+        );
+    }
+
+    /// Draw a previously-created stem plot from strided views of the data, see
+    /// [`StridedData`]. Plotting starts at the element with index `offset` and wraps around at
+    /// the end of the data, which is useful for ring buffers.
+    ///
+    /// # Panics
+    /// Will panic if the views of the positions and values have different strides.
+    pub fn plot_strided<T: PlotData>(
+        &self,
+        axis_positions: StridedData<T>,
+        stem_values: StridedData<T>,
+        offset: usize,
+    ) {
+        // If there is no data to plot, we stop here
+        let (count, offset, stride) =
+            match strided_parameters(&[axis_positions, stem_values], offset) {
+                Some(parameters) => parameters,
+                None => return,
+            };
+        if self.horizontal {
+            self.plot_horizontal(count, offset, |_, index| {
+                (
+                    axis_positions.get(index).unwrap().to_f64(),
+                    stem_values.get(index).unwrap().to_f64(),
+                )
+            });
+            return;
+        }
+        unsafe {
+            T::PLOT_STEMS(
+                self.label.as_ptr() as *const c_char,
+                axis_positions.as_ptr(),
+                stem_values.as_ptr(),
+                count,
+                self.reference,
+                offset,
+                stride,
+            );
+        }
+    }
+
+    /// Draw a previously-created stem plot from the stem values only. The stems are placed at
+    /// evenly spaced positions, see [`PlotStems::with_positions`]. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build).
+    pub fn plot_values<T: PlotData>(&self, stem_values: &[T]) {
+        self.plot_values_strided(StridedData::new(stem_values), 0);
+    }
+
+    /// Draw a previously-created stem plot from a strided view of the stem values, see
+    /// [`StridedData`] and [`PlotStems::plot_values`]. Plotting starts at the element with
+    /// index `offset` and wraps around at the end of the data.
+    pub fn plot_values_strided<T: PlotData>(&self, stem_values: StridedData<T>, offset: usize) {
+        // If there is no data to plot, we stop here
+        let (count, offset, stride) = match strided_parameters(&[stem_values], offset) {
+            Some(parameters) => parameters,
+            None => return,
+        };
+        if self.horizontal {
+            // The positions belong to the plotted points, not to the elements of the data
+            self.plot_horizontal(count, offset, |point, index| {
+                (
+                    self.position_start + self.position_scale * point as f64,
+                    stem_values.get(index).unwrap().to_f64(),
+                )
+            });
+            return;
+        }
+        unsafe {
+            T::PLOT_STEMS_VALUES(
+                self.label.as_ptr() as *const c_char,
+                stem_values.as_ptr(),
+                count,
+                self.reference,
+                self.position_scale,
+                self.position_start,
+                offset,
+                stride,
+            );
+        }
+    }
+
+    /// Internal helper function to draw horizontal stems. The version of C++ implot we link to
+    /// only has vertical stems, so horizontal ones are put together from one line for all the
+    /// stems, separated by NaN points which ImPlot leaves out, and one scatter plot for the
+    /// heads. Since both use the label of the stem plot, they form a single plot item, with one
+    /// legend entry and one color, just like vertical stems. The given function returns the
+    /// position and value of a stem, given the index of the stem in plotting order and the
+    /// index into the data, which starts at `offset` and wraps around at `count`.
+    fn plot_horizontal<F: Fn(usize, usize) -> (f64, f64)>(&self, count: i32, offset: i32, stem: F) {
+        // Both values come from strided_parameters, so they are not negative
+        let (count, offset) = (count as usize, offset as usize);
+        let stem = |point: usize| stem(point, (offset + point) % count);
+        let line_count = count
+            .checked_mul(3)
+            .filter(|line_count| *line_count <= i32::MAX as usize)
+            .expect("Too many horizontal stems to plot");
+
+        unsafe {
+            // Markers belong to the heads only, not to the ends of the lines
+            sys::ImPlot_PushStyleVar_Int(
+                sys::ImPlotStyleVar__ImPlotStyleVar_Marker as sys::ImPlotStyleVar,
+                sys::ImPlotMarker__ImPlotMarker_None,
+            );
+            plot_with_getter(
+                |index| {
+                    let (position, value) = stem(index / 3);
+                    match index % 3 {
+                        0 => ImPlotPoint {
+                            x: self.reference,
+                            y: position,
+                        },
+                        1 => ImPlotPoint {
+                            x: value,
+                            y: position,
+                        },
+                        _ => ImPlotPoint {
+                            x: f64::NAN,
+                            y: f64::NAN,
+                        },
+                    }
+                },
+                getter_of,
+                |getter, data| {
+                    sys::ImPlot_PlotLineG(
+                        self.label.as_ptr() as *const c_char,
+                        getter,
+                        data,
+                        line_count as i32,
+                    )
+                },
+            );
+            sys::ImPlot_PopStyleVar(1);
+
+            plot_with_getter(
+                |index| {
+                    let (position, value) = stem(index);
+                    ImPlotPoint {
+                        x: value,
+                        y: position,
+                    }
+                },
+                getter_of,
+                |getter, data| {
+                    sys::ImPlot_PlotScatterG(
+                        self.label.as_ptr() as *const c_char,
+                        getter,
+                        data,
+                        count as i32, // Not more than line_count, which fits
+                    )
+                },
+            );
+        }
+    }
+}
+
+/// Internal helper function to convert annotation or tag text to a CString
+///
+/// # Panics
+/// Will panic if the text contains internal null bytes.
+fn text_to_cstring<T: std::fmt::Display>(text: T) -> CString {
+    let text = text.to_string();
+    CString::new(text.as_str())
+        .unwrap_or_else(|_| panic!("Text string has internal null bytes: {}", text))
+}
+
+/// Format string used for passing already formatted text to ImPlot's variadic functions. Using
+/// this instead of the text itself as format makes sure `%` characters in the text are shown
+/// as they are instead of being interpreted by the C formatting functions.
+const TEXT_FORMAT: &[u8] = b"%s\0";
+
+/// Struct to provide functionality for annotations, which are text boxes pointing at a position
+/// in the plot. Unlike plot items, annotations don't show up in the legend.
+pub struct PlotAnnotation {
+    /// Color of the annotation background as [red, green, blue, alpha]. An alpha of zero means
+    /// no background, with text in the default text color. Defaults to that.
+    color: [f32; 4],
+
+    /// X component of the pixel offset to be used. Will be used independently of the actual plot
+    /// scaling. Defaults to 0.
+    pixel_offset_x: f32,
+
+    /// Y component of the pixel offset to be used. Will be used independently of the actual plot
+    /// scaling. Defaults to 0.
+    pixel_offset_y: f32,
+
+    /// Whether to keep the annotation within the plot area when the position is near the edge
+    clamp: bool,
+}
+
+impl PlotAnnotation {
+    /// Create a new annotation, without background and without offset. Does not draw anything
+    /// yet.
+    pub fn new() -> Self {
+        Self {
+            color: [0.0, 0.0, 0.0, 0.0],
+            pixel_offset_x: 0.0,
+            pixel_offset_y: 0.0,
+            clamp: false,
+        }
+    }
+
+    /// Set the background color of the annotation, given as [red, green, blue, alpha] with
+    /// components between 0.0 and 1.0. The text color is chosen to contrast with it.
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Add a pixel offset to the annotation. This offset will be independent of the scaling of
+    /// the plot itself, and the annotation is drawn with a leader line to its position if the
+    /// offset is nonzero.
+    pub fn with_pixel_offset(mut self, offset_x: f32, offset_y: f32) -> Self {
+        self.pixel_offset_x = offset_x;
+        self.pixel_offset_y = offset_y;
+        self
+    }
+
+    /// Set whether the annotation is kept within the plot area when its position is near the
+    /// edge of the plot.
+    pub fn with_clamp(mut self, clamp: bool) -> Self {
+        self.clamp = clamp;
+        self
+    }
+
+    /// Draw the annotation at the given position, with the given text. The text can be anything
+    /// that implements `Display`, including a `&str` or the result of `format_args!`. Use this in
+    /// closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the text contains internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "Annotation"))]
+    pub fn plot<T: std::fmt::Display>(&self, x: f64, y: f64, text: T) {
+        let text = text_to_cstring(text);
+        unsafe {
+            sys::ImPlot_Annotation_Str(
+                x,
+                y,
+                self.color_vec(),
+                self.pixel_offset_vec(),
+                self.clamp,
+                TEXT_FORMAT.as_ptr() as *const c_char,
+                text.as_ptr(),
+            );
+        }
+    }
+
+    /// Draw the annotation at the given position, showing the y value formatted with the
+    /// formatter of the current Y axis. If `round` is set, the value is rounded first. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "Annotation"))]
+    pub fn plot_value(&self, x: f64, y: f64, round: bool) {
+        unsafe {
+            sys::ImPlot_Annotation_Bool(
+                x,
+                y,
+                self.color_vec(),
+                self.pixel_offset_vec(),
+                self.clamp,
+                round,
+            );
+        }
+    }
+
+    fn color_vec(&self) -> sys::ImVec4 {
+        let [x, y, z, w] = self.color;
+        sys::ImVec4 { x, y, z, w }
+    }
+
+    fn pixel_offset_vec(&self) -> sys::ImVec2 {
+        sys::ImVec2 {
+            x: self.pixel_offset_x,
+            y: self.pixel_offset_y,
+        }
+    }
+}
+
+impl Default for PlotAnnotation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Struct to provide functionality for tags, which are text boxes shown on an axis, marking a
+/// position on it.
+pub struct PlotTag {
+    /// Color of the tag background as [red, green, blue, alpha]. An alpha of zero means no
+    /// background, with text in the default axis text color. Defaults to that.
+    color: [f32; 4],
+}
+
+impl PlotTag {
+    /// Create a new tag without background. Does not draw anything yet.
+    pub fn new() -> Self {
+        Self {
+            color: [0.0, 0.0, 0.0, 0.0],
+        }
+    }
+
+    /// Set the background color of the tag, given as [red, green, blue, alpha] with components
+    /// between 0.0 and 1.0. The text color is chosen to contrast with it.
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Draw the tag on the current X axis at the given position, with the given text. The text
+    /// can be anything that implements `Display`, including a `&str` or the result of
+    /// `format_args!`. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the text contains internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "TagX"))]
+    pub fn plot_x<T: std::fmt::Display>(&self, x: f64, text: T) {
+        let text = text_to_cstring(text);
+        unsafe {
+            sys::ImPlot_TagX_Str(
+                x,
+                self.color_vec(),
+                TEXT_FORMAT.as_ptr() as *const c_char,
+                text.as_ptr(),
+            );
+        }
+    }
+
+    /// Draw the tag on the current Y axis at the given position, with the given text. The text
+    /// can be anything that implements `Display`, including a `&str` or the result of
+    /// `format_args!`. Use this in closures passed to
+    /// [`Plot::build()`](struct.Plot.html#method.build)
+    ///
+    /// # Panics
+    /// Will panic if the text contains internal null bytes.
+    #[rustversion::attr(since(1.48), doc(alias = "TagY"))]
+    pub fn plot_y<T: std::fmt::Display>(&self, y: f64, text: T) {
+        let text = text_to_cstring(text);
+        unsafe {
+            sys::ImPlot_TagY_Str(
+                y,
+                self.color_vec(),
+                TEXT_FORMAT.as_ptr() as *const c_char,
+                text.as_ptr(),
+            );
+        }
+    }
+
+    /// Draw the tag on the current X axis at the given position, showing the position formatted
+    /// with the formatter of the axis. If `round` is set, the value is rounded first. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "TagX"))]
+    pub fn plot_x_value(&self, x: f64, round: bool) {
+        unsafe {
+            sys::ImPlot_TagX_Bool(x, self.color_vec(), round);
+        }
+    }
+
+    /// Draw the tag on the current Y axis at the given position, showing the position formatted
+    /// with the formatter of the axis. If `round` is set, the value is rounded first. Use this
+    /// in closures passed to [`Plot::build()`](struct.Plot.html#method.build)
+    #[rustversion::attr(since(1.48), doc(alias = "TagY"))]
+    pub fn plot_y_value(&self, y: f64, round: bool) {
+        unsafe {
+            sys::ImPlot_TagY_Bool(y, self.color_vec(), round);
+        }
+    }
+
+    fn color_vec(&self) -> sys::ImVec4 {
+        let [x, y, z, w] = self.color;
+        sys::ImVec4 { x, y, z, w }
+    }
+}
+
+impl Default for PlotTag {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        get_plot_limits, test_utils::run_frames, AxisFlags, Condition, Marker, Plot, StyleVar,
+        YAxisChoice,
+    };
+
+    #[test]
+    fn test_histogram_returns_max_bin_value() {
+        let values = [0.5, 1.5, 1.5, 2.5, 2.5, 2.5];
+        run_frames(1, |_, plot_ui| {
+            let mut max_bin_values = None;
+            Plot::new("Histograms").build(plot_ui, || {
+                max_bin_values = Some((
+                    PlotHistogram::new("1D")
+                        .with_bins(Bins::Count(3))
+                        .with_range([0.0, 3.0])
+                        .plot(&values),
+                    PlotHistogram::new("1D cumulative")
+                        .with_bins(Bins::Count(3))
+                        .with_range([0.0, 3.0])
+                        .with_cumulative(true)
+                        .plot(&values),
+                    PlotHistogram2D::new("2D")
+                        .with_bins(Bins::Count(3), Bins::Count(3))
+                        .plot(&values, &values),
+                ));
+            });
+            assert_eq!(max_bin_values, Some((3.0, 6.0, 3.0)));
+        });
+    }
+
+    #[test]
+    fn test_generic_elements_accept_integer_data() {
+        let x: [u16; 4] = [0, 1, 2, 3];
+        let y: [u16; 4] = [512, 1023, 0, 300];
+        run_frames(2, |_, plot_ui| {
+            Plot::new("Integer data").build(plot_ui, || {
+                PlotLine::new("line").plot(&x, &y);
+                PlotScatter::new("scatter").plot(&x, &y);
+                PlotStairs::new("stairs").plot(&x, &y);
+                PlotBars::new("bars").with_horizontal_bars().plot(&x, &y);
+                PlotStems::new("stems").plot(&x, &y);
+                PlotShaded::new("shaded").plot(&x, &y);
+                PlotShaded::new("shaded between").plot_between(&x, &x, &y);
+                PlotHeatmap::new("heatmap").plot(&y, 2, 2);
+            });
+        });
+    }
+
+    #[test]
+    fn test_horizontal_stems_cover_their_data() {
+        run_frames(2, |frame, plot_ui| {
+            let marker = plot_ui.context.style().marker();
+            let mut limits = None;
+            Plot::new("Horizontal stems")
+                .with_x_axis_flags(&AxisFlags::AUTO_FIT)
+                .with_y_axis_flags(YAxisChoice::First, &AxisFlags::AUTO_FIT)
+                .build(plot_ui, || {
+                    PlotStems::new("stems")
+                        .with_horizontal_stems()
+                        .with_reference_y(-1.0)
+                        .plot(&[1.0, 2.0], &[3.0, 4.0]);
+                    PlotStems::new("stem values")
+                        .with_horizontal_stems()
+                        .with_positions(2.0, 5.0)
+                        .plot_values(&[1u8, 2, 3]);
+                    limits = Some(get_plot_limits(None, None));
+                });
+
+            // The marker is only switched off for the lines of the stems
+            assert_eq!(plot_ui.context.style().marker(), marker);
+            // The fit from the first frame shows up in the second one
+            let limits = limits.expect("Plot was not drawn");
+            if frame == 1 {
+                assert!(limits.X.Min <= -1.0 && limits.X.Max >= 4.0);
+                assert!(limits.Y.Min <= 1.0 && limits.Y.Max >= 9.0);
+            }
+        });
+    }
+
+    /// Internal helper function to get the number of vertices in the draw list of the current
+    /// plot, which grows as plot items are rendered
+    fn plot_vertex_count() -> i32 {
+        unsafe { (*sys::ImPlot_GetPlotDrawList()).VtxBuffer.Size }
+    }
+
+    /// Internal helper function to get the number of legend entries of the current plot
+    fn plot_legend_count() -> i32 {
+        unsafe { sys::ImPlotItemGroup_GetLegendCount(&mut (*sys::ImPlot_GetCurrentPlot()).Items) }
+    }
+
+    #[test]
+    fn test_horizontal_stems_have_markers_on_heads_only() {
+        let (positions, values) = ([1.0, 2.0], [3.0, 4.0]);
+        run_frames(1, |_, plot_ui| {
+            // The marker is pushed for both plots, so it would show up on the lines as well if
+            // they had markers
+            let _marker = plot_ui.push_style_var(StyleVar::Marker(Marker::Square));
+            let mut counts = Vec::new();
+            for (title, horizontal_stems) in &[("##stems", true), ("##line and heads", false)] {
+                Plot::new(title)
+                    .x_limits([-2.0, 5.0], Condition::Always)
+                    .y1_limits([0.0, 3.0], Condition::Always)
+                    .build(plot_ui, || {
+                        let vertices_before = plot_vertex_count();
+                        if *horizontal_stems {
+                            PlotStems::new("stems")
+                                .with_horizontal_stems()
+                                .with_reference_y(-1.0)
+                                .plot(&positions, &values);
+                        } else {
+                            // What the stems should look like: a line without markers, split
+                            // by NaN points, and markers at the heads
+                            plot_ui.with_style_var(StyleVar::Marker(Marker::None), || {
+                                PlotLine::new("stems").plot(
+                                    &[-1.0, 3.0, f64::NAN, -1.0, 4.0, f64::NAN],
+                                    &[1.0, 1.0, f64::NAN, 2.0, 2.0, f64::NAN],
+                                );
+                            });
+                            PlotScatter::new("stems").plot(&values, &positions);
+                        }
+                        counts.push((plot_vertex_count() - vertices_before, plot_legend_count()));
+                    });
+            }
+            assert_eq!(counts.len(), 2);
+            assert!(counts[0].0 > 0);
+            assert_eq!(counts[0], counts[1]);
+            assert_eq!(counts[0].1, 1);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "needs more than the 1 given values")]
+    fn test_heatmap_size_mismatch_is_caught() {
+        // The check happens before anything is handed to ImPlot, so no frame is needed
+        PlotHeatmap::new("heatmap").plot(&[1u8], 100, 100);
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of values does not match")]
+    fn test_bar_group_size_mismatch_is_caught() {
+        // Checked before anything is handed to ImPlot, like the heatmap size
+        PlotBarGroups::new(&["a", "b"]).plot(&[1.0, 2.0], u32::MAX);
+    }
+
+    #[test]
+    fn test_heatmap_of_empty_data_is_skipped() {
+        let values: [f64; 0] = [];
+        PlotHeatmap::new("heatmap").plot(&values, 0, 0);
+    }
+
+    #[test]
+    fn test_getter_panics_are_caught_and_kept() {
+        let calls = std::cell::Cell::new(0);
+        let mut data = GetterData {
+            getter: |index: usize| {
+                calls.set(calls.get() + 1);
+                if index == 1 {
+                    panic!("getter failed");
+                }
+                ImPlotPoint {
+                    x: index as f64,
+                    y: 2.0,
+                }
+            },
+            panic: None,
+        };
+        let point = call_getter(&mut data, 0);
+        assert_eq!([point.x, point.y], [0.0, 2.0]);
+        assert!(call_getter(&mut data, 1).x.is_nan());
+        assert!(call_getter(&mut data, 2).y.is_nan());
+        assert_eq!(calls.get(), 2);
+        assert_eq!(
+            data.panic.unwrap().downcast_ref::<&str>(),
+            Some(&"getter failed")
+        );
+    }
+
+    #[test]
+    fn test_getters_plot_within_frames() {
+        let largest_index = std::cell::Cell::new(0);
+        let square = |index: usize| {
+            largest_index.set(largest_index.get().max(index));
+            ImPlotPoint {
+                x: index as f64,
+                y: (index * index) as f64,
+            }
+        };
+        run_frames(2, |frame, plot_ui| {
+            let mut limits = None;
+            Plot::new("Getters")
+                .with_x_axis_flags(&AxisFlags::AUTO_FIT)
+                .with_y_axis_flags(YAxisChoice::First, &AxisFlags::AUTO_FIT)
+                .build(plot_ui, || {
+                    PlotLine::new("line").plot_getter(3, square);
+                    // Stairs use the getter variant returning the point by value
+                    PlotStairs::new("stairs").plot_getter(4, square);
+                    limits = Some(get_plot_limits(None, None));
+                });
+
+            let limits = limits.expect("Plot was not drawn");
+            if frame == 1 {
+                assert!(limits.X.Max >= 3.0 && limits.Y.Max >= 9.0);
+            }
+        });
+        assert_eq!(largest_index.get(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "getter failed within a frame")]
+    fn test_getter_panic_is_resumed_after_plotting() {
+        run_frames(1, |_, plot_ui| {
+            Plot::new("Panicking getter").build(plot_ui, || {
+                PlotLine::new("line").plot_getter(3, |index| {
+                    if index == 1 {
+                        panic!("getter failed within a frame");
+                    }
+                    ImPlotPoint {
+                        x: index as f64,
+                        y: 0.0,
+                    }
+                });
+            });
+        });
+    }
+
+    #[test]
+    fn test_digital_bool_getter_converts_values() {
+        let x = [0.0, 1.0, 2.0];
+        let y = [false, true, false];
+        let mut data = DigitalBoolData { x: &x, y: &y };
+        let mut point = ImPlotPoint { x: -1.0, y: -1.0 };
+        unsafe {
+            digital_bool_getter(
+                &mut data as *mut DigitalBoolData as *mut std::os::raw::c_void,
+                1,
+                &mut point,
             );
         }
+        assert_eq!([point.x, point.y], [1.0, 1.0]);
     }
 }
@@ -0,0 +1,234 @@
+//! # Style module
+//!
+//! This module defines `Style`, which gives typed access to the style of an ImPlot context:
+//! the sizes, paddings and weights used for drawing, the colors of the plot elements, the
+//! colormap and the time formatting options. Temporary changes that only apply to some plots
+//! are better done with the push/pop style functions. The color presets are applied through
+//! the context, e.g. with `Context::use_dark_colors`.
+use crate::{sys, Colormap, Marker, PlotColorElement};
+use std::cell::UnsafeCell;
+
+/// The style of an ImPlot context, obtained through
+/// [`Context::style()`](struct.Context.html#method.style) and
+/// [`Context::style_mut()`](struct.Context.html#method.style_mut). Changes apply to everything
+/// plotted afterwards.
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotStyle"))]
+#[repr(transparent)]
+pub struct Style {
+    // ImPlot itself changes the style while plotting, for example when style variables are
+    // pushed, so shared references to this struct can't assume the contents stay the same.
+    raw: UnsafeCell<sys::ImPlotStyle>,
+}
+
+/// Generate getters and setters for style fields that are plain `f32` values
+macro_rules! f32_accessors {
+    ($($(#[$doc:meta])* $field:ident, $getter:ident, $setter:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[inline]
+            pub fn $getter(&self) -> f32 {
+                self.raw().$field
+            }
+
+            $(#[$doc])*
+            #[inline]
+            pub fn $setter(&mut self, value: f32) {
+                self.raw_mut().$field = value;
+            }
+        )*
+    };
+}
+
+/// Generate getters and setters for style fields that are `ImVec2` values, which are exposed
+/// as `[f32; 2]` like in imgui-rs
+macro_rules! vec2_accessors {
+    ($($(#[$doc:meta])* $field:ident, $getter:ident, $setter:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[inline]
+            pub fn $getter(&self) -> [f32; 2] {
+                let value = self.raw().$field;
+                [value.x, value.y]
+            }
+
+            $(#[$doc])*
+            #[inline]
+            pub fn $setter(&mut self, [x, y]: [f32; 2]) {
+                self.raw_mut().$field = sys::ImVec2 { x, y };
+            }
+        )*
+    };
+}
+
+/// Generate getters and setters for style fields that are `bool` values
+macro_rules! bool_accessors {
+    ($($(#[$doc:meta])* $field:ident, $getter:ident, $setter:ident;)*) => {
+        $(
+            $(#[$doc])*
+            #[inline]
+            pub fn $getter(&self) -> bool {
+                self.raw().$field
+            }
+
+            $(#[$doc])*
+            #[inline]
+            pub fn $setter(&mut self, value: bool) {
+                self.raw_mut().$field = value;
+            }
+        )*
+    };
+}
+
+impl Style {
+    /// Internal helper function to read the raw style
+    #[inline]
+    fn raw(&self) -> &sys::ImPlotStyle {
+        // ImPlot only changes the style while functions of this crate are running, which
+        // can't happen during the lifetime of this short borrow.
+        unsafe { &*self.raw.get() }
+    }
+
+    /// Internal helper function to change the raw style
+    #[inline]
+    fn raw_mut(&mut self) -> &mut sys::ImPlotStyle {
+        self.raw.get_mut()
+    }
+
+    f32_accessors! {
+        /// Line weight of plot items, in pixels. Defaults to 1.
+        LineWeight, line_weight, set_line_weight;
+        /// Size of markers, in pixels (roughly the marker's "radius"). Defaults to 4.
+        MarkerSize, marker_size, set_marker_size;
+        /// Outline weight of markers, in pixels. Defaults to 1.
+        MarkerWeight, marker_weight, set_marker_weight;
+        /// Alpha modifier applied to all plot item fills. Defaults to 1.
+        FillAlpha, fill_alpha, set_fill_alpha;
+        /// Width of error bar whiskers, in pixels. Defaults to 5.
+        ErrorBarSize, error_bar_size, set_error_bar_size;
+        /// Weight of error bar whiskers, in pixels. Defaults to 1.5.
+        ErrorBarWeight, error_bar_weight, set_error_bar_weight;
+        /// Height of digital channels at a value of 1, in pixels. Defaults to 8.
+        DigitalBitHeight, digital_bit_height, set_digital_bit_height;
+        /// Gap between digital channels, in pixels. Defaults to 4.
+        DigitalBitGap, digital_bit_gap, set_digital_bit_gap;
+        /// Thickness of the border around the plot area. Defaults to 1.
+        PlotBorderSize, plot_border_size, set_plot_border_size;
+        /// Alpha multiplier applied to minor axis grid lines. Defaults to 0.25.
+        MinorAlpha, minor_alpha, set_minor_alpha;
+    }
+
+    vec2_accessors! {
+        /// Lengths of major ticks for the X and Y axes
+        MajorTickLen, major_tick_len, set_major_tick_len;
+        /// Lengths of minor ticks for the X and Y axes
+        MinorTickLen, minor_tick_len, set_minor_tick_len;
+        /// Line thickness of major ticks
+        MajorTickSize, major_tick_size, set_major_tick_size;
+        /// Line thickness of minor ticks
+        MinorTickSize, minor_tick_size, set_minor_tick_size;
+        /// Line thickness of major grid lines
+        MajorGridSize, major_grid_size, set_major_grid_size;
+        /// Line thickness of minor grid lines
+        MinorGridSize, minor_grid_size, set_minor_grid_size;
+        /// Padding between the widget frame and the plot area and/or labels
+        PlotPadding, plot_padding, set_plot_padding;
+        /// Padding between axis labels, tick labels and the plot edge
+        LabelPadding, label_padding, set_label_padding;
+        /// Padding of the legend from the edge of the plot
+        LegendPadding, legend_padding, set_legend_padding;
+        /// Padding inside the legend, from its edges
+        LegendInnerPadding, legend_inner_padding, set_legend_inner_padding;
+        /// Spacing between legend entries
+        LegendSpacing, legend_spacing, set_legend_spacing;
+        /// Padding between the plot edge and the mouse position text
+        MousePosPadding, mouse_pos_padding, set_mouse_pos_padding;
+        /// Padding of the text in annotations
+        AnnotationPadding, annotation_padding, set_annotation_padding;
+        /// Additional fit padding as a fraction of the fit extents, e.g. [0.1, 0.1] adds 10%
+        /// to the fit extents of X and Y
+        FitPadding, fit_padding, set_fit_padding;
+        /// Default size of plots, used when a size of [0, 0] is given
+        PlotDefaultSize, plot_default_size, set_plot_default_size;
+        /// Minimum size plot frames can be shrunk to
+        PlotMinSize, plot_min_size, set_plot_min_size;
+    }
+
+    bool_accessors! {
+        /// Whether lines are drawn anti-aliased
+        AntiAliasedLines, anti_aliased_lines, set_anti_aliased_lines;
+        /// Whether time axes show local time instead of UTC
+        UseLocalTime, use_local_time, set_use_local_time;
+        /// Whether dates on time axes are formatted according to ISO 8601
+        UseISO8601, use_iso8601, set_use_iso8601;
+        /// Whether times on time axes use a 24 hour clock
+        Use24HourClock, use_24_hour_clock, set_use_24_hour_clock;
+    }
+
+    /// Default marker of plot items. Returns `None` if the raw value is not a known marker.
+    pub fn marker(&self) -> Option<Marker> {
+        Marker::from_raw(self.raw().Marker)
+    }
+
+    /// Set the default marker of plot items
+    pub fn set_marker(&mut self, marker: Marker) {
+        self.raw_mut().Marker = marker as i32;
+    }
+
+    /// Color of the given plot element, as [red, green, blue, alpha]. An alpha of -1 means the
+    /// color is picked automatically.
+    #[rustversion::attr(since(1.48), doc(alias = "Colors"))]
+    pub fn color(&self, element: PlotColorElement) -> [f32; 4] {
+        let color = self.raw().Colors[element as usize];
+        [color.x, color.y, color.z, color.w]
+    }
+
+    /// Set the color of the given plot element, as [red, green, blue, alpha]. Use an alpha of
+    /// -1 to have the color picked automatically.
+    pub fn set_color(&mut self, element: PlotColorElement, [x, y, z, w]: [f32; 4]) {
+        self.raw_mut().Colors[element as usize] = sys::ImVec4 { x, y, z, w };
+    }
+
+    /// Default colormap. Returns `None` if the colormap is not one of the presets.
+    pub fn colormap(&self) -> Option<Colormap> {
+        Colormap::from_raw(self.raw().Colormap)
+    }
+
+    /// Set the default colormap
+    pub fn set_colormap(&mut self, colormap: Colormap) {
+        // "as" casts saturate as of Rust 1.45. This is safe here, the enum values are small.
+        self.raw_mut().Colormap = colormap as i32;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::with_context;
+
+    #[test]
+    fn test_style_changes_are_applied() {
+        with_context(|context| {
+            let style = context.style_mut();
+            style.set_line_weight(2.5);
+            style.set_plot_padding([3.0, 4.0]);
+            style.set_use_24_hour_clock(true);
+            style.set_marker(Marker::Diamond);
+            style.set_colormap(Colormap::Greys);
+            style.set_color(PlotColorElement::AxisGrid, [0.1, 0.2, 0.3, 0.4]);
+
+            let style = context.style();
+            assert_eq!(style.line_weight(), 2.5);
+            assert_eq!(style.plot_padding(), [3.0, 4.0]);
+            assert!(style.use_24_hour_clock());
+            assert_eq!(style.marker(), Some(Marker::Diamond));
+            assert_eq!(style.colormap(), Some(Colormap::Greys));
+            assert_eq!(
+                style.color(PlotColorElement::AxisGrid),
+                [0.1, 0.2, 0.3, 0.4]
+            );
+
+            context.use_auto_colors();
+            assert_eq!(context.style().color(PlotColorElement::Line)[3], -1.0);
+        });
+    }
+}
@@ -0,0 +1,410 @@
+//! # Subplots module
+//!
+//! This module defines the `Subplots` struct, which is used to lay out several plots in a grid,
+//! with optionally linked axes, shared legend items and resizable rows and columns. It also
+//! defines `AlignedPlots`, which aligns the axes of plots that are laid out by other means.
+use crate::{Context, PlotUi};
+use bitflags::bitflags;
+use implot_sys as sys;
+use std::ffi::CString;
+use std::marker::PhantomData;
+use sys::ImVec2;
+
+const DEFAULT_SUBPLOTS_SIZE_X: f32 = 400.0;
+const DEFAULT_SUBPLOTS_SIZE_Y: f32 = 400.0;
+
+#[rustversion::attr(since(1.48), doc(alias = "ImPlotSubplotFlags"))]
+bitflags! {
+    /// Flags for customizing subplot behavior and interaction. Documentation copied from implot.h
+    /// for convenience.
+    #[repr(transparent)]
+    pub struct SubplotFlags: u32 {
+        /// "Default" according to original docs
+        const NONE = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_None;
+        /// The subplot title will not be displayed (titles are also hidden if preceeded by double
+        /// hashes, e.g. "##MySubplot")
+        const NO_TITLE = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_NoTitle;
+        /// The legend will not be displayed (only applicable if `SHARE_ITEMS` is enabled)
+        const NO_LEGEND = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_NoLegend;
+        /// The user will not be able to open context menus with right-click
+        const NO_MENUS = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_NoMenus;
+        /// Resize splitters between subplot cells will be not be provided
+        const NO_RESIZE = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_NoResize;
+        /// Subplot edges will not be aligned vertically or horizontally
+        const NO_ALIGN = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_NoAlign;
+        /// Items across all subplots will be shared and rendered into a single legend entry
+        const SHARE_ITEMS = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_ShareItems;
+        /// Link the y-axis limits of all plots in each row (does not apply to auxiliary axes)
+        const LINK_ROWS = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_LinkRows;
+        /// Link the x-axis limits of all plots in each column (does not apply to auxiliary axes)
+        const LINK_COLS = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_LinkCols;
+        /// Link the x-axis limits in every plot in the subplot (does not apply to auxiliary axes)
+        const LINK_ALL_X = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_LinkAllX;
+        /// Link the y-axis limits in every plot in the subplot (does not apply to auxiliary axes)
+        const LINK_ALL_Y = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_LinkAllY;
+        /// Subplots are added in column major order instead of the default row major order
+        const COL_MAJOR = sys::ImPlotSubplotFlags__ImPlotSubplotFlags_ColMajor;
+    }
+}
+
+/// Struct to represent a grid of subplots. Every plot built while the grid is active goes into
+/// the next cell of the grid, in row major order unless `SubplotFlags::COL_MAJOR` is set.
+///
+/// `Subplots` is to be used (within an imgui window) with the following pattern:
+/// ```no_run
+/// # use implot::{Plot, Subplots, SubplotFlags};
+/// let plotting_context = implot::Context::create();
+/// let plot_ui = plotting_context.get_plot_ui();
+/// // Keep these around between frames to persist resizing done by the user
+/// let mut row_ratios = [1.0, 2.0];
+/// let mut col_ratios = [1.0, 1.0, 1.0];
+/// Subplots::new("my subplots", 2, 3)
+///     .size([600.0, 400.0])
+///     .with_flags(&SubplotFlags::LINK_ALL_X)
+///     .row_ratios(&mut row_ratios)
+///     .col_ratios(&mut col_ratios)
+///     .build(&plot_ui, || {
+///         for cell in 0..6 {
+///             Plot::new(&format!("Plot {}", cell)).build(&plot_ui, || {
+///                 // Do things such as plotting lines
+///             });
+///         }
+///     });
+/// ```
+/// (If you are coming from the C++ implementation or the C bindings: build() calls both
+/// begin() and end() internally)
+pub struct Subplots<'r> {
+    /// Title of the subplot grid, shown on top. Stored as CString because that's what we'll use
+    /// afterwards, and this ensures the CString itself will stay alive long enough.
+    title: CString,
+    /// Number of rows of the grid
+    rows: i32,
+    /// Number of columns of the grid
+    cols: i32,
+    /// Size of the whole grid in [x, y] direction, in the same units imgui uses.
+    size: [f32; 2],
+    /// Flags relating to the subplots
+    flags: SubplotFlags,
+    /// Relative heights of the rows, if set. ImPlot writes to these when the user resizes rows.
+    row_ratios: Option<&'r mut [f32]>,
+    /// Relative widths of the columns, if set. ImPlot writes to these when the user resizes
+    /// columns.
+    col_ratios: Option<&'r mut [f32]>,
+}
+
+impl<'r> Subplots<'r> {
+    /// Create a new grid of subplots with the given number of rows and columns. Does not draw
+    /// anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the title string contains internal null bytes, or if there are no rows or
+    /// no columns.
+    pub fn new(title: &str, rows: u32, cols: u32) -> Self {
+        assert!(
+            rows > 0 && cols > 0,
+            "Subplots need at least one row and one column"
+        );
+        Self {
+            title: CString::new(title)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", title)),
+            rows: rows as i32, // "as" casts saturate as of Rust 1.45
+            cols: cols as i32,
+            size: [DEFAULT_SUBPLOTS_SIZE_X, DEFAULT_SUBPLOTS_SIZE_Y],
+            flags: SubplotFlags::NONE,
+            row_ratios: None,
+            col_ratios: None,
+        }
+    }
+
+    /// Sets the size of the whole grid, given as [size_x, size_y]. Units are the same as
+    /// what imgui uses.
+    #[inline]
+    pub fn size(mut self, size: [f32; 2]) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the subplot flags, see the help for `SubplotFlags` for what the available flags are
+    #[inline]
+    pub fn with_flags(mut self, flags: &SubplotFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Set the relative heights of the rows. When the user resizes rows, ImPlot writes the new
+    /// ratios back into the slice, so keeping the slice around between frames persists the
+    /// resizing.
+    ///
+    /// # Panics
+    /// Will panic if the length of the slice does not match the number of rows.
+    #[inline]
+    pub fn row_ratios(mut self, ratios: &'r mut [f32]) -> Self {
+        assert_eq!(
+            ratios.len(),
+            self.rows as usize,
+            "Number of row ratios does not match the number of rows"
+        );
+        self.row_ratios = Some(ratios);
+        self
+    }
+
+    /// Set the relative widths of the columns. When the user resizes columns, ImPlot writes the
+    /// new ratios back into the slice, so keeping the slice around between frames persists the
+    /// resizing.
+    ///
+    /// # Panics
+    /// Will panic if the length of the slice does not match the number of columns.
+    #[inline]
+    pub fn col_ratios(mut self, ratios: &'r mut [f32]) -> Self {
+        assert_eq!(
+            ratios.len(),
+            self.cols as usize,
+            "Number of column ratios does not match the number of columns"
+        );
+        self.col_ratios = Some(ratios);
+        self
+    }
+
+    /// Attempt to show the subplot grid. If this returns a token, the grid will actually be
+    /// drawn. In this case, build the plots that go into the cells, and then call `end()` on the
+    /// token when done. If none was returned, that means the grid is not rendered.
+    ///
+    /// For a convenient implementation of all this, use [`build()`](struct.Subplots.html#method.build)
+    /// instead.
+    #[rustversion::attr(since(1.48), doc(alias = "BeginSubplots"))]
+    pub fn begin(&mut self, plot_ui: &PlotUi) -> Option<SubplotsToken<'_>> {
+        let should_render = unsafe {
+            let size_vec: ImVec2 = ImVec2 {
+                x: self.size[0],
+                y: self.size[1],
+            };
+            sys::ImPlot_BeginSubplots(
+                self.title.as_ptr(),
+                self.rows,
+                self.cols,
+                size_vec,
+                self.flags.bits() as sys::ImPlotSubplotFlags,
+                self.row_ratios
+                    .as_mut()
+                    .map_or(std::ptr::null_mut(), |ratios| ratios.as_mut_ptr()),
+                self.col_ratios
+                    .as_mut()
+                    .map_or(std::ptr::null_mut(), |ratios| ratios.as_mut_ptr()),
+            )
+        };
+
+        if should_render {
+            Some(SubplotsToken {
+                context: plot_ui.context,
+                subplots_title: self.title.clone(),
+                _ratios: PhantomData,
+            })
+        } else {
+            // Like with plots, end() does not have to be called if we don't render.
+            None
+        }
+    }
+
+    /// Creates the subplot grid and runs a closure to construct the plots in its cells. This
+    /// internally calls `begin` and `end`.
+    ///
+    /// Note: the closure is not called if ImPlot::BeginSubplots() returned false.
+    #[rustversion::attr(since(1.48), doc(alias = "BeginSubplots"))]
+    #[rustversion::attr(since(1.48), doc(alias = "EndSubplots"))]
+    pub fn build<F: FnOnce()>(mut self, plot_ui: &PlotUi, f: F) {
+        if let Some(token) = self.begin(plot_ui) {
+            f();
+            token.end()
+        }
+    }
+}
+
+/// Tracks a subplot grid that must be ended by calling `.end()`
+pub struct SubplotsToken<'r> {
+    context: *const Context,
+    /// For better error messages
+    subplots_title: CString,
+    /// ImPlot may write to the row and column ratios while the grid is active, so they stay
+    /// mutably borrowed until the token is gone.
+    _ratios: PhantomData<&'r mut [f32]>,
+}
+
+impl SubplotsToken<'_> {
+    /// End a previously begin()'ed subplot grid.
+    #[rustversion::attr(since(1.48), doc(alias = "EndSubplots"))]
+    pub fn end(mut self) {
+        self.context = std::ptr::null();
+        unsafe { sys::ImPlot_EndSubplots() };
+    }
+}
+
+impl Drop for SubplotsToken<'_> {
+    fn drop(&mut self) {
+        if !self.context.is_null() && !std::thread::panicking() {
+            panic!(
+                "Warning: A SubplotsToken for subplots \"{:?}\" was not called end() on",
+                self.subplots_title
+            );
+        }
+    }
+}
+
+/// Struct to represent a group of aligned plots. All plots built while the group is active
+/// share the padding for their axis labels, so their axes line up even if their tick labels
+/// differ in width. By default, the group aligns plots that are stacked vertically (i.e. their
+/// Y axes), use `vertical(false)` for plots next to each other.
+///
+/// `AlignedPlots` is to be used (within an imgui window) with the following pattern:
+/// ```no_run
+/// # use implot::{AlignedPlots, Plot};
+/// let plotting_context = implot::Context::create();
+/// let plot_ui = plotting_context.get_plot_ui();
+/// AlignedPlots::new("my group").build(&plot_ui, || {
+///     Plot::new("Small values").build(&plot_ui, || {
+///         // Plot things with short tick labels
+///     });
+///     Plot::new("Large values").build(&plot_ui, || {
+///         // Plot things with long tick labels
+///     });
+/// });
+/// ```
+pub struct AlignedPlots {
+    /// ID of the group. Stored as CString because that's what we'll use afterwards, and this
+    /// ensures the CString itself will stay alive long enough.
+    group_id: CString,
+    /// Whether the plots are stacked vertically (aligning Y axes) or horizontally (aligning
+    /// X axes)
+    vertical: bool,
+}
+
+impl AlignedPlots {
+    /// Create a new group of vertically aligned plots. Does not do anything yet.
+    ///
+    /// # Panics
+    /// Will panic if the group ID string contains internal null bytes.
+    pub fn new(group_id: &str) -> Self {
+        Self {
+            group_id: CString::new(group_id)
+                .unwrap_or_else(|_| panic!("String contains internal null bytes: {}", group_id)),
+            vertical: true,
+        }
+    }
+
+    /// Set whether the plots of the group are stacked vertically, which aligns their Y axes, or
+    /// placed next to each other horizontally, which aligns their X axes.
+    #[inline]
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+
+    /// Attempt to start the group. If this returns a token, build the plots of the group and
+    /// then call `end()` on the token. If none was returned, the group is not active and
+    /// `end()` does not need to be called.
+    ///
+    /// For a convenient implementation of all this, use [`build()`](struct.AlignedPlots.html#method.build)
+    /// instead.
+    #[rustversion::attr(since(1.48), doc(alias = "BeginAlignedPlots"))]
+    pub fn begin(&self, plot_ui: &PlotUi) -> Option<AlignedPlotsToken> {
+        let should_render =
+            unsafe { sys::ImPlot_BeginAlignedPlots(self.group_id.as_ptr(), self.vertical) };
+
+        if should_render {
+            Some(AlignedPlotsToken {
+                context: plot_ui.context,
+                group_id: self.group_id.clone(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Starts the group and runs a closure to build the plots in it. This internally calls
+    /// `begin` and `end`.
+    ///
+    /// Note: the closure is not called if ImPlot::BeginAlignedPlots() returned false.
+    #[rustversion::attr(since(1.48), doc(alias = "BeginAlignedPlots"))]
+    #[rustversion::attr(since(1.48), doc(alias = "EndAlignedPlots"))]
+    pub fn build<F: FnOnce()>(self, plot_ui: &PlotUi, f: F) {
+        if let Some(token) = self.begin(plot_ui) {
+            f();
+            token.end()
+        }
+    }
+}
+
+/// Tracks a group of aligned plots that must be ended by calling `.end()`
+pub struct AlignedPlotsToken {
+    context: *const Context,
+    /// For better error messages
+    group_id: CString,
+}
+
+impl AlignedPlotsToken {
+    /// End a previously begin()'ed group of aligned plots.
+    #[rustversion::attr(since(1.48), doc(alias = "EndAlignedPlots"))]
+    pub fn end(mut self) {
+        self.context = std::ptr::null();
+        unsafe { sys::ImPlot_EndAlignedPlots() };
+    }
+}
+
+impl Drop for AlignedPlotsToken {
+    fn drop(&mut self) {
+        if !self.context.is_null() && !std::thread::panicking() {
+            panic!(
+                "Warning: An AlignedPlotsToken for group \"{:?}\" was not called end() on",
+                self.group_id
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::run_frames, Plot};
+
+    #[test]
+    fn test_plots_are_built_into_cells() {
+        let mut row_ratios = [1.0, 2.0];
+        let mut col_ratios = [1.0, 1.0, 1.0];
+        run_frames(2, |_, plot_ui| {
+            let mut plots_drawn = 0;
+            Subplots::new("Grid", 2, 3)
+                .with_flags(&(SubplotFlags::LINK_ALL_X | SubplotFlags::SHARE_ITEMS))
+                .row_ratios(&mut row_ratios)
+                .col_ratios(&mut col_ratios)
+                .build(plot_ui, || {
+                    for cell in 0..6 {
+                        Plot::new(&format!("Cell {}", cell)).build(plot_ui, || {
+                            plots_drawn += 1;
+                        });
+                    }
+                });
+            assert_eq!(plots_drawn, 6);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of row ratios does not match the number of rows")]
+    fn test_row_ratio_count_is_checked() {
+        let mut row_ratios = [1.0];
+        let _ = Subplots::new("Grid", 2, 2).row_ratios(&mut row_ratios);
+    }
+
+    #[test]
+    fn test_aligned_plots_group() {
+        run_frames(1, |_, plot_ui| {
+            let mut plots_drawn = 0;
+            AlignedPlots::new("Group").build(plot_ui, || {
+                for title in &["Aligned 1", "Aligned 2"] {
+                    Plot::new(title).build(plot_ui, || {
+                        plots_drawn += 1;
+                    });
+                }
+            });
+            assert_eq!(plots_drawn, 2);
+        });
+    }
+}
@@ -0,0 +1,460 @@
+//! # Theme module
+//!
+//! This module defines `Theme`, a serializable snapshot of an ImPlot style. Themes can be
+//! captured from and applied to a context, and stored as TOML or JSON, which makes it easy to
+//! share a consistent look between applications. This module is only available with the `serde`
+//! feature, TOML and JSON support additionally need the `toml` and `serde_json` features
+//! respectively, which enable `serde` as well.
+use crate::{sys, Colormap, Marker, PlotColorElement, Style};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde_json")]
+use serde_json_crate as serde_json;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+#[cfg(feature = "toml")]
+use toml_crate as toml;
+
+/// Names of the colorable plot elements as they are used in themes, which are the names of the
+/// `PlotColorElement` variants
+const COLOR_ELEMENTS: [(&str, PlotColorElement); 21] = [
+    ("Line", PlotColorElement::Line),
+    ("Fill", PlotColorElement::Fill),
+    ("MarkerOutline", PlotColorElement::MarkerOutline),
+    ("MarkerFill", PlotColorElement::MarkerFill),
+    ("ErrorBar", PlotColorElement::ErrorBar),
+    ("FrameBg", PlotColorElement::FrameBg),
+    ("PlotBg", PlotColorElement::PlotBg),
+    ("PlotBorder", PlotColorElement::PlotBorder),
+    ("LegendBackground", PlotColorElement::LegendBackground),
+    ("LegendBorder", PlotColorElement::LegendBorder),
+    ("LegendText", PlotColorElement::LegendText),
+    ("TitleText", PlotColorElement::TitleText),
+    ("InlayText", PlotColorElement::InlayText),
+    ("AxisText", PlotColorElement::AxisText),
+    ("AxisGrid", PlotColorElement::AxisGrid),
+    ("AxisTick", PlotColorElement::AxisTick),
+    ("AxisBg", PlotColorElement::AxisBg),
+    ("AxisBgHovered", PlotColorElement::AxisBgHovered),
+    ("AxisBgActive", PlotColorElement::AxisBgActive),
+    ("Selection", PlotColorElement::Selection),
+    ("Crosshairs", PlotColorElement::Crosshairs),
+];
+
+/// Errors that can occur when loading, saving or converting themes
+#[derive(Debug)]
+pub enum ThemeError {
+    /// Reading or writing the theme file failed
+    Io(std::io::Error),
+    /// The file extension is neither `toml` nor `json`, or support for the format is not
+    /// enabled
+    UnsupportedFormat(String),
+    /// The theme names a color element that does not exist
+    UnknownColorElement(String),
+    /// Parsing a TOML theme failed
+    #[cfg(feature = "toml")]
+    TomlDeserialize(toml::de::Error),
+    /// Converting the theme to TOML failed
+    #[cfg(feature = "toml")]
+    TomlSerialize(toml::ser::Error),
+    /// Parsing a JSON theme or converting a theme to JSON failed
+    #[cfg(feature = "serde_json")]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(error) => write!(f, "Theme file could not be accessed: {}", error),
+            ThemeError::UnsupportedFormat(extension) => {
+                write!(f, "Unsupported theme file format: \"{}\"", extension)
+            }
+            ThemeError::UnknownColorElement(name) => {
+                write!(f, "Unknown color element in theme: \"{}\"", name)
+            }
+            #[cfg(feature = "toml")]
+            ThemeError::TomlDeserialize(error) => write!(f, "Invalid TOML theme: {}", error),
+            #[cfg(feature = "toml")]
+            ThemeError::TomlSerialize(error) => {
+                write!(f, "Theme could not be converted to TOML: {}", error)
+            }
+            #[cfg(feature = "serde_json")]
+            ThemeError::Json(error) => write!(f, "Invalid JSON theme: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ThemeError::Io(error) => Some(error),
+            #[cfg(feature = "toml")]
+            ThemeError::TomlDeserialize(error) => Some(error),
+            #[cfg(feature = "toml")]
+            ThemeError::TomlSerialize(error) => Some(error),
+            #[cfg(feature = "serde_json")]
+            ThemeError::Json(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ThemeError {
+    fn from(error: std::io::Error) -> Self {
+        ThemeError::Io(error)
+    }
+}
+
+/// A complete ImPlot style: all sizes, weights and paddings, the colors of the plot elements,
+/// the colormap and the time formatting options. See [`Style`] for what the values mean.
+///
+/// Themes are captured from a context with
+/// [`Context::capture_theme()`](struct.Context.html#method.capture_theme) and applied with
+/// [`Context::apply_theme()`](struct.Context.html#method.apply_theme). Applying a captured
+/// theme restores exactly the style it was captured from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Theme {
+    /// Line weight, see [`Style::line_weight`]
+    pub line_weight: f32,
+    /// Default marker, `None` leaves the marker unchanged when the theme is applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<Marker>,
+    /// Marker size, see [`Style::marker_size`]
+    pub marker_size: f32,
+    /// Marker outline weight, see [`Style::marker_weight`]
+    pub marker_weight: f32,
+    /// Alpha modifier of fills, see [`Style::fill_alpha`]
+    pub fill_alpha: f32,
+    /// Error bar whisker width, see [`Style::error_bar_size`]
+    pub error_bar_size: f32,
+    /// Error bar whisker weight, see [`Style::error_bar_weight`]
+    pub error_bar_weight: f32,
+    /// Height of digital channels, see [`Style::digital_bit_height`]
+    pub digital_bit_height: f32,
+    /// Gap between digital channels, see [`Style::digital_bit_gap`]
+    pub digital_bit_gap: f32,
+    /// Thickness of the plot border, see [`Style::plot_border_size`]
+    pub plot_border_size: f32,
+    /// Alpha multiplier of minor grid lines, see [`Style::minor_alpha`]
+    pub minor_alpha: f32,
+    /// Lengths of major ticks, see [`Style::major_tick_len`]
+    pub major_tick_len: [f32; 2],
+    /// Lengths of minor ticks, see [`Style::minor_tick_len`]
+    pub minor_tick_len: [f32; 2],
+    /// Thickness of major ticks, see [`Style::major_tick_size`]
+    pub major_tick_size: [f32; 2],
+    /// Thickness of minor ticks, see [`Style::minor_tick_size`]
+    pub minor_tick_size: [f32; 2],
+    /// Thickness of major grid lines, see [`Style::major_grid_size`]
+    pub major_grid_size: [f32; 2],
+    /// Thickness of minor grid lines, see [`Style::minor_grid_size`]
+    pub minor_grid_size: [f32; 2],
+    /// Padding around the plot area, see [`Style::plot_padding`]
+    pub plot_padding: [f32; 2],
+    /// Padding of axis and tick labels, see [`Style::label_padding`]
+    pub label_padding: [f32; 2],
+    /// Padding of the legend, see [`Style::legend_padding`]
+    pub legend_padding: [f32; 2],
+    /// Padding inside the legend, see [`Style::legend_inner_padding`]
+    pub legend_inner_padding: [f32; 2],
+    /// Spacing between legend entries, see [`Style::legend_spacing`]
+    pub legend_spacing: [f32; 2],
+    /// Padding of the mouse position text, see [`Style::mouse_pos_padding`]
+    pub mouse_pos_padding: [f32; 2],
+    /// Padding of annotation text, see [`Style::annotation_padding`]
+    pub annotation_padding: [f32; 2],
+    /// Additional fit padding, see [`Style::fit_padding`]
+    pub fit_padding: [f32; 2],
+    /// Default plot size, see [`Style::plot_default_size`]
+    pub plot_default_size: [f32; 2],
+    /// Minimum plot size, see [`Style::plot_min_size`]
+    pub plot_min_size: [f32; 2],
+    /// Colormap, `None` leaves the colormap unchanged when the theme is applied. This is what
+    /// is captured if the style uses a colormap that is not one of the presets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colormap: Option<Colormap>,
+    /// Whether lines are anti-aliased, see [`Style::anti_aliased_lines`]
+    pub anti_aliased_lines: bool,
+    /// Whether time axes show local time, see [`Style::use_local_time`]
+    pub use_local_time: bool,
+    /// Whether dates are formatted as ISO 8601, see [`Style::use_iso8601`]
+    pub use_iso8601: bool,
+    /// Whether times use a 24 hour clock, see [`Style::use_24_hour_clock`]
+    pub use_24_hour_clock: bool,
+    /// Colors of the plot elements as [red, green, blue, alpha], keyed by the names of the
+    /// `PlotColorElement` variants. Elements that are missing are left unchanged when the
+    /// theme is applied, names of elements that don't exist are ignored.
+    pub colors: BTreeMap<String, [f32; 4]>,
+}
+
+impl Theme {
+    /// Capture the given style as a theme
+    pub(crate) fn capture(style: &Style) -> Self {
+        Self {
+            line_weight: style.line_weight(),
+            marker: style.marker(),
+            marker_size: style.marker_size(),
+            marker_weight: style.marker_weight(),
+            fill_alpha: style.fill_alpha(),
+            error_bar_size: style.error_bar_size(),
+            error_bar_weight: style.error_bar_weight(),
+            digital_bit_height: style.digital_bit_height(),
+            digital_bit_gap: style.digital_bit_gap(),
+            plot_border_size: style.plot_border_size(),
+            minor_alpha: style.minor_alpha(),
+            major_tick_len: style.major_tick_len(),
+            minor_tick_len: style.minor_tick_len(),
+            major_tick_size: style.major_tick_size(),
+            minor_tick_size: style.minor_tick_size(),
+            major_grid_size: style.major_grid_size(),
+            minor_grid_size: style.minor_grid_size(),
+            plot_padding: style.plot_padding(),
+            label_padding: style.label_padding(),
+            legend_padding: style.legend_padding(),
+            legend_inner_padding: style.legend_inner_padding(),
+            legend_spacing: style.legend_spacing(),
+            mouse_pos_padding: style.mouse_pos_padding(),
+            annotation_padding: style.annotation_padding(),
+            fit_padding: style.fit_padding(),
+            plot_default_size: style.plot_default_size(),
+            plot_min_size: style.plot_min_size(),
+            colormap: style.colormap(),
+            anti_aliased_lines: style.anti_aliased_lines(),
+            use_local_time: style.use_local_time(),
+            use_iso8601: style.use_iso8601(),
+            use_24_hour_clock: style.use_24_hour_clock(),
+            colors: COLOR_ELEMENTS
+                .iter()
+                .map(|(name, element)| (name.to_string(), style.color(*element)))
+                .collect(),
+        }
+    }
+
+    /// Apply the theme to the given style. Colors of elements that don't exist are ignored,
+    /// themes loaded through the functions of this struct are checked for those already.
+    pub(crate) fn apply(&self, style: &mut Style) {
+        style.set_line_weight(self.line_weight);
+        if let Some(marker) = self.marker {
+            style.set_marker(marker);
+        }
+        style.set_marker_size(self.marker_size);
+        style.set_marker_weight(self.marker_weight);
+        style.set_fill_alpha(self.fill_alpha);
+        style.set_error_bar_size(self.error_bar_size);
+        style.set_error_bar_weight(self.error_bar_weight);
+        style.set_digital_bit_height(self.digital_bit_height);
+        style.set_digital_bit_gap(self.digital_bit_gap);
+        style.set_plot_border_size(self.plot_border_size);
+        style.set_minor_alpha(self.minor_alpha);
+        style.set_major_tick_len(self.major_tick_len);
+        style.set_minor_tick_len(self.minor_tick_len);
+        style.set_major_tick_size(self.major_tick_size);
+        style.set_minor_tick_size(self.minor_tick_size);
+        style.set_major_grid_size(self.major_grid_size);
+        style.set_minor_grid_size(self.minor_grid_size);
+        style.set_plot_padding(self.plot_padding);
+        style.set_label_padding(self.label_padding);
+        style.set_legend_padding(self.legend_padding);
+        style.set_legend_inner_padding(self.legend_inner_padding);
+        style.set_legend_spacing(self.legend_spacing);
+        style.set_mouse_pos_padding(self.mouse_pos_padding);
+        style.set_annotation_padding(self.annotation_padding);
+        style.set_fit_padding(self.fit_padding);
+        style.set_plot_default_size(self.plot_default_size);
+        style.set_plot_min_size(self.plot_min_size);
+        if let Some(colormap) = self.colormap {
+            style.set_colormap(colormap);
+        }
+        style.set_anti_aliased_lines(self.anti_aliased_lines);
+        style.set_use_local_time(self.use_local_time);
+        style.set_use_iso8601(self.use_iso8601);
+        style.set_use_24_hour_clock(self.use_24_hour_clock);
+        COLOR_ELEMENTS.iter().for_each(|(name, element)| {
+            if let Some(color) = self.colors.get(*name) {
+                style.set_color(*element, *color);
+            }
+        });
+    }
+
+    /// Internal helper function to check that all colors of the theme belong to existing
+    /// color elements
+    fn check_color_elements(&self) -> Result<(), ThemeError> {
+        match self
+            .colors
+            .keys()
+            .find(|name| !COLOR_ELEMENTS.iter().any(|(known, _)| known == name))
+        {
+            Some(name) => Err(ThemeError::UnknownColorElement(name.clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Internal helper function to create a theme from ImPlot's default style, with the given
+    /// color preset applied
+    fn preset(apply_colors: unsafe extern "C" fn(*mut sys::ImPlotStyle)) -> Self {
+        unsafe {
+            let raw = sys::ImPlotStyle_ImPlotStyle();
+            assert_ne!(raw, std::ptr::null_mut());
+            apply_colors(raw);
+            // This is fine because Style is a transparent wrapper around ImPlotStyle
+            let theme = Self::capture(&*(raw as *const Style));
+            sys::ImPlotStyle_destroy(raw);
+            theme
+        }
+    }
+
+    /// The default ImPlot style, with colors picked automatically based on the imgui style
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsAuto"))]
+    pub fn auto() -> Self {
+        Self::preset(sys::ImPlot_StyleColorsAuto)
+    }
+
+    /// The default ImPlot style with the classic colors
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsClassic"))]
+    pub fn classic() -> Self {
+        Self::preset(sys::ImPlot_StyleColorsClassic)
+    }
+
+    /// The default ImPlot style with dark colors
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsDark"))]
+    pub fn dark() -> Self {
+        Self::preset(sys::ImPlot_StyleColorsDark)
+    }
+
+    /// The default ImPlot style with light colors
+    #[rustversion::attr(since(1.48), doc(alias = "StyleColorsLight"))]
+    pub fn light() -> Self {
+        Self::preset(sys::ImPlot_StyleColorsLight)
+    }
+
+    /// Parse a theme from TOML
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(toml: &str) -> Result<Self, ThemeError> {
+        let theme: Self = toml::from_str(toml).map_err(ThemeError::TomlDeserialize)?;
+        theme.check_color_elements()?;
+        Ok(theme)
+    }
+
+    /// Convert the theme to TOML
+    #[cfg(feature = "toml")]
+    pub fn to_toml_string(&self) -> Result<String, ThemeError> {
+        toml::to_string_pretty(self).map_err(ThemeError::TomlSerialize)
+    }
+
+    /// Parse a theme from JSON
+    #[cfg(feature = "serde_json")]
+    pub fn from_json_str(json: &str) -> Result<Self, ThemeError> {
+        let theme: Self = serde_json::from_str(json).map_err(ThemeError::Json)?;
+        theme.check_color_elements()?;
+        Ok(theme)
+    }
+
+    /// Convert the theme to JSON
+    #[cfg(feature = "serde_json")]
+    pub fn to_json_string(&self) -> Result<String, ThemeError> {
+        serde_json::to_string_pretty(self).map_err(ThemeError::Json)
+    }
+
+    /// Load a theme from a file. The format is chosen by the file extension, which has to be
+    /// `toml` or `json`, with the corresponding feature enabled.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        match file_extension(path).as_str() {
+            #[cfg(feature = "toml")]
+            "toml" => Self::from_toml_str(&std::fs::read_to_string(path)?),
+            #[cfg(feature = "serde_json")]
+            "json" => Self::from_json_str(&std::fs::read_to_string(path)?),
+            extension => Err(ThemeError::UnsupportedFormat(extension.to_string())),
+        }
+    }
+
+    /// Save the theme to a file. The format is chosen by the file extension, which has to be
+    /// `toml` or `json`, with the corresponding feature enabled.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ThemeError> {
+        let path = path.as_ref();
+        match file_extension(path).as_str() {
+            #[cfg(feature = "toml")]
+            "toml" => Ok(std::fs::write(path, self.to_toml_string()?)?),
+            #[cfg(feature = "serde_json")]
+            "json" => Ok(std::fs::write(path, self.to_json_string()?)?),
+            extension => Err(ThemeError::UnsupportedFormat(extension.to_string())),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::auto()
+    }
+}
+
+/// Internal helper function to get the lowercase extension of a theme file path
+fn file_extension(path: &Path) -> String {
+    path.extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::with_context;
+
+    /// Internal helper function to get the raw bytes of a style, for checking that applying
+    /// a theme restores exactly the captured state
+    fn style_bytes(style: &Style) -> Vec<u8> {
+        unsafe {
+            std::slice::from_raw_parts(
+                style as *const Style as *const u8,
+                std::mem::size_of::<sys::ImPlotStyle>(),
+            )
+            .to_vec()
+        }
+    }
+
+    #[test]
+    fn test_captured_theme_reapplies_identical_style() {
+        with_context(|context| {
+            context.use_dark_colors();
+            context.style_mut().set_plot_padding([7.0, 3.0]);
+            context.style_mut().set_colormap(Colormap::Viridis);
+            context.style_mut().set_use_iso8601(true);
+            let original = style_bytes(context.style());
+            let theme = context.capture_theme();
+
+            context.use_light_colors();
+            context.style_mut().set_plot_padding([1.0, 1.0]);
+            context.style_mut().set_fill_alpha(0.1);
+            context.style_mut().set_colormap(Colormap::Jet);
+            assert_ne!(style_bytes(context.style()), original);
+
+            context.apply_theme(&theme);
+            assert_eq!(style_bytes(context.style()), original);
+        });
+    }
+
+    #[cfg(all(feature = "toml", feature = "serde_json"))]
+    #[test]
+    fn test_theme_round_trips_through_toml_and_json() {
+        let mut theme = Theme::dark();
+        theme.line_weight = 1.3;
+        theme.marker = Some(Marker::Diamond);
+        theme.colormap = Some(Colormap::Twilight);
+        theme
+            .colors
+            .insert("Line".to_string(), [0.1, 0.2, 0.3, 0.4]);
+
+        let toml = theme.to_toml_string().unwrap();
+        assert_eq!(Theme::from_toml_str(&toml).unwrap(), theme);
+        let json = theme.to_json_string().unwrap();
+        assert_eq!(Theme::from_json_str(&json).unwrap(), theme);
+
+        let mut invalid = theme;
+        invalid.colors.insert("Lines".to_string(), [0.0; 4]);
+        let json = invalid.to_json_string().unwrap();
+        match Theme::from_json_str(&json) {
+            Err(ThemeError::UnknownColorElement(name)) => assert_eq!(name, "Lines"),
+            other => panic!("Expected an unknown color element error, got {:?}", other),
+        }
+    }
+}